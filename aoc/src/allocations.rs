@@ -0,0 +1,133 @@
+//! An opt-in global allocator that counts allocations and tracks the peak heap size, so the
+//! runner can report them for each phase of a solution.
+//!
+//! To enable it, install it in the binary:
+//!
+//! ```no_run
+//! #[global_allocator]
+//! static ALLOCATOR: aoc::allocations::CountingAllocator = aoc::allocations::CountingAllocator;
+//! ```
+//!
+//! The counters are global, so the numbers are only meaningful while one phase runs at a time.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the system allocator, and counts what goes through it.
+pub struct CountingAllocator;
+
+fn add_bytes(size: usize) {
+    let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        add_bytes(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        add_bytes(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+
+    /// Counted as an allocation, since growing a `Vec` usually means copying it.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        if new_size >= layout.size() {
+            add_bytes(new_size - layout.size());
+        } else {
+            CURRENT_BYTES.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Whether the `CountingAllocator` is installed. The standard library allocates before `main`
+/// runs, so the counter is never zero if it is.
+pub fn is_counting() -> bool {
+    ALLOCATIONS.load(Ordering::Relaxed) > 0
+}
+
+/// What a piece of code allocated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    /// The number of allocations and reallocations.
+    pub allocations: usize,
+    /// The largest amount of heap memory in use at any one time, on top of what was in use
+    /// before.
+    pub peak_bytes: usize,
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} allocations, peak {}", self.allocations, format_bytes(self.peak_bytes))
+    }
+}
+
+/// Runs `func`, and returns what it allocated if the `CountingAllocator` is installed.
+pub fn measure<F, R>(func: F) -> (R, Option<Usage>)
+    where F: FnOnce() -> R
+{
+    if !is_counting() {
+        return (func(), None);
+    }
+    let start_allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start_bytes = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(start_bytes, Ordering::Relaxed);
+    let result = func();
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - start_allocations,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(start_bytes),
+    };
+    (result, Some(usage))
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1 << 20 {
+        format!("{:.1} MiB", bytes as f64 / (1 << 20) as f64)
+    } else if bytes >= 1 << 10 {
+        format!("{:.1} KiB", bytes as f64 / (1 << 10) as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+#[test]
+fn test_format() {
+    assert_eq!(Usage { allocations: 3, peak_bytes: 100 }.to_string(), "3 allocations, peak 100 B");
+    assert_eq!(format_bytes(1536), "1.5 KiB");
+    assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+}
+
+#[cfg(test)]
+#[global_allocator]
+static TEST_ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_measure() {
+    assert!(is_counting());
+    let (byte, usage) = measure(|| {
+        let bytes = vec![1u8; 1 << 20];
+        // Keep the optimizer from removing the allocation.
+        unsafe { std::ptr::read_volatile(&bytes[12345]) }
+    });
+    let usage = usage.unwrap();
+    // Other tests may allocate at the same time, so only lower bounds are certain.
+    assert_eq!(byte, 1);
+    assert!(usage.allocations >= 1);
+    assert!(usage.peak_bytes >= 1 << 20);
+}
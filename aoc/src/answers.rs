@@ -0,0 +1,63 @@
+//! Store of confirmed answers, used to verify the output of solutions.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+pub fn answer_file_name(year: u32, day: u32, part: u32) -> String {
+    format!("answers/{}/{:02}-{}.answer", year, day, part)
+}
+
+/// Returns the confirmed answer for the given part, if one has been recorded.
+pub fn load(year: u32, day: u32, part: u32) -> Option<String> {
+    fs::read_to_string(answer_file_name(year, day, part))
+        .ok()
+        .map(|answer| answer.trim().to_string())
+}
+
+/// Records a confirmed answer. Refuses to overwrite a different answer that was recorded earlier.
+pub fn record(year: u32, day: u32, part: u32, answer: &str) -> Result<(), Box<dyn Error>> {
+    let answer = answer.trim();
+    if answer.is_empty() || answer.contains('\n') {
+        return Err(format!("invalid answer {:?}", answer).into());
+    }
+    let file_name = answer_file_name(year, day, part);
+    match load(year, day, part) {
+        Some(prev) if prev == answer => return Ok(()),
+        Some(prev) => return Err(format!(
+                "{} already contains a different answer ({}); remove it first if it is wrong",
+                file_name, prev).into()),
+        None => {},
+    }
+    if let Some(dir) = Path::new(&file_name).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&file_name, format!("{}\n", answer))?;
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Ok,
+    Mismatch { expected: String },
+    Unknown,
+}
+
+pub fn verify(year: u32, day: u32, part: u32, answer: &str) -> Verdict {
+    match load(year, day, part) {
+        Some(expected) if expected == answer.trim() => Verdict::Ok,
+        Some(expected) => Verdict::Mismatch { expected },
+        None => Verdict::Unknown,
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Ok => write!(f, "OK"),
+            Verdict::Mismatch { expected } => write!(f, "MISMATCH, expected {}", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
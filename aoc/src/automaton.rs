@@ -0,0 +1,476 @@
+//! Cellular automata in the style of Conway's Game of Life: each cell is alive or dead, and its
+//! next state depends only on its current state and how many of its neighbors are alive.
+//!
+//! The space the cells live in is a `Topology`, and the way they evolve is a `Rule`. Two backends
+//! store the cells: `Dense` keeps a flag for every cell of a `Finite` topology, and `Sparse` keeps
+//! only the live cells, so it also works on infinite topologies.
+
+use crate::{Grid, ParseError, DIRECTIONS8};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// The space that the cells live in, and which cells are neighbors.
+pub trait Topology {
+    type Cell: Copy + Eq + Ord + Hash;
+
+    /// Calls `f` with each neighbor of `cell`.
+    fn for_each_neighbor<F: FnMut(Self::Cell)>(&self, cell: Self::Cell, f: F);
+}
+
+/// A topology with a finite number of cells, which are numbered from 0.
+pub trait Finite: Topology {
+    fn num_cells(&self) -> usize;
+    fn index(&self, cell: Self::Cell) -> usize;
+    fn cell(&self, index: usize) -> Self::Cell;
+}
+
+/// The infinite square grid, where each cell has 8 neighbors.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Square;
+
+impl Topology for Square {
+    type Cell = (i64, i64);
+
+    fn for_each_neighbor<F: FnMut((i64, i64))>(&self, (x, y): (i64, i64), mut f: F) {
+        for &(dx, dy) in &DIRECTIONS8 {
+            f((x + dx, y + dy));
+        }
+    }
+}
+
+/// A `width` by `height` square grid, where cells have up to 8 neighbors. Cells are numbered in
+/// row-major order, like the cells of a `Grid`.
+#[derive(Debug, Clone, Copy)]
+pub struct Rect {
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Topology for Rect {
+    type Cell = (i64, i64);
+
+    fn for_each_neighbor<F: FnMut((i64, i64))>(&self, (x, y): (i64, i64), mut f: F) {
+        for &(dx, dy) in &DIRECTIONS8 {
+            let (x, y) = (x + dx, y + dy);
+            if 0 <= x && x < self.width && 0 <= y && y < self.height {
+                f((x, y));
+            }
+        }
+    }
+}
+
+impl Finite for Rect {
+    fn num_cells(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    fn index(&self, (x, y): (i64, i64)) -> usize {
+        (y * self.width + x) as usize
+    }
+
+    fn cell(&self, index: usize) -> (i64, i64) {
+        (index as i64 % self.width, index as i64 / self.width)
+    }
+}
+
+/// The infinite hexagonal grid in axial coordinates: the neighbors of `(x, y)` are `(x ± 1, y)`,
+/// `(x, y ± 1)`, `(x + 1, y - 1)` and `(x - 1, y + 1)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hex;
+
+impl Hex {
+    pub const DIRECTIONS: [(i64, i64); 6] = [(1, -1), (1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1)];
+}
+
+impl Topology for Hex {
+    type Cell = (i64, i64);
+
+    fn for_each_neighbor<F: FnMut((i64, i64))>(&self, (x, y): (i64, i64), mut f: F) {
+        for &(dx, dy) in &Hex::DIRECTIONS {
+            f((x + dx, y + dy));
+        }
+    }
+}
+
+/// The infinite N-dimensional grid, with cells identified by `[i64; N]`, where each cell has
+/// `3^N - 1` neighbors.
+#[derive(Debug, Clone)]
+pub struct Lattice<P> {
+    offsets: Vec<P>,
+}
+
+impl<P> Lattice<P>
+    where P: Copy + Default + AsRef<[i64]> + AsMut<[i64]>
+{
+    pub fn new() -> Lattice<P> {
+        let dimensions = P::default().as_ref().len();
+        let offsets = (0..3usize.pow(dimensions as u32))
+            .map(|mut n| {
+                let mut offset = P::default();
+                for coordinate in offset.as_mut() {
+                    *coordinate = (n % 3) as i64 - 1;
+                    n /= 3;
+                }
+                offset
+            })
+            .filter(|offset| offset.as_ref().iter().any(|&coordinate| coordinate != 0))
+            .collect();
+        Lattice { offsets }
+    }
+}
+
+impl<P> Default for Lattice<P>
+    where P: Copy + Default + AsRef<[i64]> + AsMut<[i64]>
+{
+    fn default() -> Lattice<P> {
+        Lattice::new()
+    }
+}
+
+impl<P> Topology for Lattice<P>
+    where P: Copy + Eq + Ord + Hash + AsRef<[i64]> + AsMut<[i64]>
+{
+    type Cell = P;
+
+    fn for_each_neighbor<F: FnMut(P)>(&self, cell: P, mut f: F) {
+        for offset in &self.offsets {
+            let mut neighbor = cell;
+            for (coordinate, delta) in neighbor.as_mut().iter_mut().zip(offset.as_ref()) {
+                *coordinate += delta;
+            }
+            f(neighbor);
+        }
+    }
+}
+
+/// An arbitrary finite topology, given by the list of neighbors of each cell.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    pub neighbors: Vec<Vec<usize>>,
+}
+
+impl Topology for Graph {
+    type Cell = usize;
+
+    fn for_each_neighbor<F: FnMut(usize)>(&self, cell: usize, f: F) {
+        self.neighbors[cell].iter().copied().for_each(f);
+    }
+}
+
+impl Finite for Graph {
+    fn num_cells(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    fn index(&self, cell: usize) -> usize {
+        cell
+    }
+
+    fn cell(&self, index: usize) -> usize {
+        index
+    }
+}
+
+/// Decides whether a cell is alive in the next generation.
+pub trait Rule {
+    fn next(&self, alive: bool, live_neighbors: usize) -> bool;
+}
+
+impl<F: Fn(bool, usize) -> bool> Rule for F {
+    fn next(&self, alive: bool, live_neighbors: usize) -> bool {
+        self(alive, live_neighbors)
+    }
+}
+
+/// A rule in B/S notation, such as `B3/S23` for Conway's Game of Life: a dead cell is born if it
+/// has as many live neighbors as one of the digits after `B`, and a live cell survives if it has
+/// as many as one of the digits after `S`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifeRule {
+    birth: u16,
+    survival: u16,
+}
+
+impl LifeRule {
+    pub fn conway() -> LifeRule {
+        "B3/S23".parse().unwrap()
+    }
+}
+
+impl FromStr for LifeRule {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<LifeRule, ParseError> {
+        let (birth, survival) = crate::parse::split_once(s, "/")?;
+        let digits = |part: &str, prefix: char| {
+            if !part.starts_with(prefix) {
+                return Err(ParseError::at(s, part, format!("expected {:?}", prefix)));
+            }
+            let mut mask = 0;
+            for (i, c) in part.char_indices().skip(1) {
+                match c.to_digit(10) {
+                    Some(digit) => mask |= 1 << digit,
+                    None => return Err(ParseError::at(s, &part[i..], format!("expected a digit, found {:?}", c))),
+                }
+            }
+            Ok(mask)
+        };
+        Ok(LifeRule { birth: digits(birth, 'B')?, survival: digits(survival, 'S')? })
+    }
+}
+
+impl Rule for LifeRule {
+    fn next(&self, alive: bool, live_neighbors: usize) -> bool {
+        let mask = if alive { self.survival } else { self.birth };
+        live_neighbors < 16 && mask & (1 << live_neighbors) != 0
+    }
+}
+
+#[test]
+fn test_life_rule() {
+    let rule = LifeRule::conway();
+    assert_eq!((rule.next(false, 3), rule.next(false, 2), rule.next(true, 2), rule.next(true, 4)), (true, false, true, false));
+    assert!(!rule.next(true, 80));
+    assert_eq!("B/S012345678".parse::<LifeRule>().map(|rule| rule.next(true, 8)), Ok(true));
+    assert_eq!("B3/23".parse::<LifeRule>(), Err(ParseError::new(1, 4, "expected 'S'")));
+    assert_eq!("B3x/S23".parse::<LifeRule>(), Err(ParseError::new(1, 3, "expected a digit, found 'x'")));
+    assert_eq!("B3S23".parse::<LifeRule>(), Err(ParseError::new(1, 6, "expected \"/\"")));
+}
+
+/// Where a sequence of generations starts repeating itself, as found by
+/// `Automaton::find_cycle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first generation that occurs again later.
+    pub start: usize,
+    /// The number of generations after which it occurs again; 1 for a fixed point.
+    pub period: usize,
+}
+
+/// The state of a cellular automaton, which evolves one generation at a time.
+pub trait Automaton {
+    /// Identifies the set of live cells, to detect cycles.
+    type Key: Eq + Hash;
+
+    /// Advances by one generation, and returns whether any cell changed.
+    fn step<R: Rule>(&mut self, rule: &R) -> bool;
+
+    /// The number of live cells.
+    fn population(&self) -> usize;
+
+    fn key(&self) -> Self::Key;
+
+    fn run<R: Rule>(&mut self, rule: &R, generations: usize) {
+        for _ in 0..generations {
+            self.step(rule);
+        }
+    }
+
+    /// Advances until the live cells stop changing, and returns the number of generations that
+    /// changed something. Never returns if the automaton doesn't settle.
+    fn run_to_fixed_point<R: Rule>(&mut self, rule: &R) -> usize {
+        let mut generations = 0;
+        while self.step(rule) {
+            generations += 1;
+        }
+        generations
+    }
+
+    /// Advances until a generation occurs for the second time, and returns where the cycle
+    /// starts and its length. Never returns if the automaton grows forever.
+    fn find_cycle<R: Rule>(&mut self, rule: &R) -> Cycle {
+        let mut seen = HashMap::new();
+        let mut generation = 0;
+        loop {
+            if let Some(start) = seen.insert(self.key(), generation) {
+                return Cycle { start, period: generation - start };
+            }
+            self.step(rule);
+            generation += 1;
+        }
+    }
+}
+
+/// Stores whether every cell of a finite topology is alive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dense<T> {
+    topology: T,
+    alive: Vec<bool>,
+}
+
+impl<T: Finite> Dense<T> {
+    /// Creates an automaton where the given cells are alive.
+    pub fn new<I: IntoIterator<Item = T::Cell>>(topology: T, live_cells: I) -> Dense<T> {
+        let mut alive = vec![false; topology.num_cells()];
+        for cell in live_cells {
+            alive[topology.index(cell)] = true;
+        }
+        Dense { topology, alive }
+    }
+
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    pub fn is_alive(&self, cell: T::Cell) -> bool {
+        self.alive[self.topology.index(cell)]
+    }
+
+    pub fn set(&mut self, cell: T::Cell, alive: bool) {
+        let index = self.topology.index(cell);
+        self.alive[index] = alive;
+    }
+
+    pub fn live_cells(&self) -> impl Iterator<Item = T::Cell> + '_ {
+        (0..self.alive.len()).filter(move |&i| self.alive[i]).map(move |i| self.topology.cell(i))
+    }
+}
+
+impl Dense<Rect> {
+    pub fn from_grid(grid: &Grid<bool>) -> Dense<Rect> {
+        let topology = Rect { width: grid.width(), height: grid.height() };
+        Dense { topology, alive: grid.iter().copied().collect() }
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::from_vec(self.topology.width, self.topology.height, self.alive.clone())
+    }
+}
+
+impl<T: Finite> Automaton for Dense<T> {
+    type Key = Vec<bool>;
+
+    fn step<R: Rule>(&mut self, rule: &R) -> bool {
+        let next = (0..self.alive.len())
+            .map(|i| {
+                let mut live_neighbors = 0;
+                self.topology.for_each_neighbor(self.topology.cell(i), |neighbor| {
+                    if self.alive[self.topology.index(neighbor)] {
+                        live_neighbors += 1;
+                    }
+                });
+                rule.next(self.alive[i], live_neighbors)
+            })
+            .collect::<Vec<_>>();
+        let changed = next != self.alive;
+        self.alive = next;
+        changed
+    }
+
+    fn population(&self) -> usize {
+        self.alive.iter().filter(|&&alive| alive).count()
+    }
+
+    fn key(&self) -> Vec<bool> {
+        self.alive.clone()
+    }
+}
+
+/// Stores only the live cells, which suits infinite topologies. Only the live cells and their
+/// neighbors are considered in each generation, so the rule must not bring cells without live
+/// neighbors to life.
+#[derive(Debug, Clone)]
+pub struct Sparse<T: Topology> {
+    topology: T,
+    alive: HashSet<T::Cell>,
+}
+
+impl<T: Topology> Sparse<T> {
+    /// Creates an automaton where the given cells are alive.
+    pub fn new<I: IntoIterator<Item = T::Cell>>(topology: T, live_cells: I) -> Sparse<T> {
+        Sparse { topology, alive: live_cells.into_iter().collect() }
+    }
+
+    pub fn topology(&self) -> &T {
+        &self.topology
+    }
+
+    pub fn is_alive(&self, cell: T::Cell) -> bool {
+        self.alive.contains(&cell)
+    }
+
+    pub fn set(&mut self, cell: T::Cell, alive: bool) {
+        if alive {
+            self.alive.insert(cell);
+        } else {
+            self.alive.remove(&cell);
+        }
+    }
+
+    pub fn live_cells(&self) -> impl Iterator<Item = T::Cell> + '_ {
+        self.alive.iter().copied()
+    }
+}
+
+impl<T: Topology> Automaton for Sparse<T> {
+    type Key = Vec<T::Cell>;
+
+    fn step<R: Rule>(&mut self, rule: &R) -> bool {
+        assert!(!rule.next(false, 0), "the sparse backend cannot bring cells without live neighbors to life");
+        let mut live_neighbors = HashMap::<T::Cell, usize>::with_capacity(2 * self.alive.len());
+        for &cell in &self.alive {
+            live_neighbors.entry(cell).or_default();
+            self.topology.for_each_neighbor(cell, |neighbor| *live_neighbors.entry(neighbor).or_default() += 1);
+        }
+        let next = live_neighbors
+            .into_iter()
+            .filter(|(cell, count)| rule.next(self.alive.contains(cell), *count))
+            .map(|(cell, _)| cell)
+            .collect::<HashSet<_>>();
+        let changed = next != self.alive;
+        self.alive = next;
+        changed
+    }
+
+    fn population(&self) -> usize {
+        self.alive.len()
+    }
+
+    fn key(&self) -> Vec<T::Cell> {
+        let mut cells = self.alive.iter().copied().collect::<Vec<_>>();
+        cells.sort_unstable();
+        cells
+    }
+}
+
+#[test]
+fn test_dense() {
+    let grid = crate::parse::grid(".....\n..#..\n..#..\n..#..\n.....\n", |c| Some(c == '#')).unwrap();
+    let mut blinker = Dense::from_grid(&grid);
+    blinker.step(&LifeRule::conway());
+    assert_eq!(blinker.to_grid().map(|&alive| if alive { '#' } else { '.' }).to_string(), ".....\n.....\n.###.\n.....\n.....\n");
+    assert_eq!(blinker.live_cells().collect::<Vec<_>>(), vec![(1, 2), (2, 2), (3, 2)]);
+    assert_eq!(blinker.find_cycle(&LifeRule::conway()), Cycle { start: 0, period: 2 });
+
+    let mut block = Dense::new(Rect { width: 4, height: 4 }, vec![(1, 1), (2, 1), (1, 2)]);
+    assert_eq!(block.run_to_fixed_point(&LifeRule::conway()), 1);
+    assert_eq!((block.population(), block.is_alive((2, 2))), (4, true));
+
+    // A path of 3 cells where each cell copies its left neighbor.
+    let path = Graph { neighbors: vec![vec![], vec![0], vec![1]] };
+    let mut shift = Dense::new(path, vec![0]);
+    let rule = |_alive, live_neighbors| live_neighbors == 1;
+    shift.step(&rule);
+    assert_eq!(shift.live_cells().collect::<Vec<_>>(), vec![1]);
+    assert_eq!(shift.run_to_fixed_point(&rule), 2);
+    assert_eq!(shift.population(), 0);
+}
+
+#[test]
+fn test_sparse() {
+    let glider = vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+    let mut life = Sparse::new(Square, glider.clone());
+    life.run(&LifeRule::conway(), 4);
+    let mut moved = life.live_cells().collect::<Vec<_>>();
+    moved.sort_unstable();
+    assert_eq!(moved, Sparse::new(Square, glider.iter().map(|&(x, y)| (x + 1, y + 1))).key());
+
+    let mut count = 0;
+    Hex.for_each_neighbor((0, 0), |_| count += 1);
+    Lattice::<[i64; 4]>::new().for_each_neighbor([0; 4], |_| count += 1);
+    assert_eq!(count, 6 + 80);
+
+    let mut block = Sparse::new(Lattice::<[i64; 2]>::new(), vec![[0, 0], [1, 0], [0, 1], [1, 1]]);
+    assert_eq!(block.find_cycle(&LifeRule::conway()), Cycle { start: 0, period: 1 });
+}
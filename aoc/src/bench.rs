@@ -0,0 +1,300 @@
+//! Statistical benchmarking of solutions, with regression checks against a saved baseline.
+
+use crate::Phase;
+use serde_json::{json, Map, Value};
+use std::error::Error;
+use std::fs;
+use std::iter;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub const BASELINE_FILE_NAME: &str = "bench/baseline.json";
+
+/// Time spent running the function before measuring starts. Always at least one run.
+const WARMUP_TIME: Duration = Duration::from_secs(1);
+
+/// Time after which no more samples are taken, provided we have at least `MIN_SAMPLES`.
+const MEASUREMENT_TIME: Duration = Duration::from_secs(5);
+const MIN_SAMPLES: usize = 5;
+const MAX_SAMPLES: usize = 1000;
+
+/// Relative change in median that is considered significant. The change must also exceed twice
+/// the standard deviation, so that noisy measurements are not flagged.
+const SIGNIFICANT_CHANGE: f64 = 0.05;
+
+/// Timing statistics over a number of runs. All times are in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<f64>) -> Stats {
+        assert!(!samples.is_empty());
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = samples.len();
+        let median = if n % 2 == 0 {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2.0
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            samples.iter().map(|s| (s - mean) * (s - mean)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Stats { samples: n, min: samples[0], median, mean, stddev: variance.sqrt() }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "samples": self.samples,
+            "min_ns": self.min,
+            "median_ns": self.median,
+            "mean_ns": self.mean,
+            "stddev_ns": self.stddev,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Stats> {
+        Some(Stats {
+            samples: value.get("samples")?.as_u64()? as usize,
+            min: value.get("min_ns")?.as_f64()?,
+            median: value.get("median_ns")?.as_f64()?,
+            mean: value.get("mean_ns")?.as_f64()?,
+            stddev: value.get("stddev_ns")?.as_f64()?,
+        })
+    }
+}
+
+#[test]
+fn test_stats_from_samples() {
+    let stats = Stats::from_samples(vec![4.0, 1.0, 3.0, 2.0]);
+    assert_eq!(stats.samples, 4);
+    assert!((stats.min - 1.0).abs() < 1e-9);
+    assert!((stats.median - 2.5).abs() < 1e-9);
+    assert!((stats.mean - 2.5).abs() < 1e-9);
+    assert!((stats.stddev - (5.0f64 / 3.0).sqrt()).abs() < 1e-9);
+
+    let stats = Stats::from_samples(vec![7.0]);
+    assert_eq!((stats.min, stats.median, stats.mean, stats.stddev), (7.0, 7.0, 7.0, 0.0));
+
+    assert_eq!(Stats::from_json(&stats.to_json()), Some(stats));
+}
+
+/// Timing results of earlier runs, keyed by year, day and phase.
+pub struct Baseline {
+    entries: Map<String, Value>,
+}
+
+impl Baseline {
+    pub fn load() -> Result<Baseline, Box<dyn Error>> {
+        let entries = if Path::new(BASELINE_FILE_NAME).exists() {
+            match serde_json::from_str(&fs::read_to_string(BASELINE_FILE_NAME)?)? {
+                Value::Object(entries) => entries,
+                _ => return Err("it does not contain a JSON object".into()),
+            }
+        } else {
+            Map::new()
+        };
+        Ok(Baseline { entries })
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = Path::new(BASELINE_FILE_NAME).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(&self.entries)?;
+        fs::write(BASELINE_FILE_NAME, json + "\n")?;
+        Ok(())
+    }
+
+    fn get(&self, year: u32, day: u32, phase: Phase) -> Option<Stats> {
+        self.entries.get(&Self::key(year, day, phase)).and_then(Stats::from_json)
+    }
+
+    fn set(&mut self, year: u32, day: u32, phase: Phase, stats: &Stats) {
+        self.entries.insert(Self::key(year, day, phase), stats.to_json());
+    }
+
+    fn key(year: u32, day: u32, phase: Phase) -> String {
+        match phase {
+            Phase::Parse => format!("{}/{:02}/parse", year, day),
+            Phase::Part(part) => format!("{}/{:02}/{}", year, day, part),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Change {
+    Regression,
+    Improvement,
+    Insignificant,
+}
+
+fn compare(baseline: &Stats, current: &Stats) -> Change {
+    let delta = current.median - baseline.median;
+    let noise = 2.0 * baseline.stddev.max(current.stddev);
+    if delta.abs() <= noise || delta.abs() <= SIGNIFICANT_CHANGE * baseline.median {
+        Change::Insignificant
+    } else if delta > 0.0 {
+        Change::Regression
+    } else {
+        Change::Improvement
+    }
+}
+
+#[test]
+fn test_compare() {
+    let stats = |median, stddev| Stats { samples: 10, min: median, median, mean: median, stddev };
+    assert_eq!(compare(&stats(100.0, 1.0), &stats(101.0, 1.0)), Change::Insignificant);
+    assert_eq!(compare(&stats(100.0, 1.0), &stats(110.0, 1.0)), Change::Regression);
+    assert_eq!(compare(&stats(100.0, 1.0), &stats(90.0, 1.0)), Change::Improvement);
+    assert_eq!(compare(&stats(100.0, 10.0), &stats(110.0, 1.0)), Change::Insignificant);
+}
+
+/// Runs `func` repeatedly, collecting one timing sample per run.
+pub fn measure<F, R>(mut func: F) -> Stats
+    where F: FnMut() -> R
+{
+    let warmup_start = Instant::now();
+    loop {
+        func();
+        if warmup_start.elapsed() >= WARMUP_TIME {
+            break;
+        }
+    }
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    while samples.len() < MIN_SAMPLES || (samples.len() < MAX_SAMPLES && start.elapsed() < MEASUREMENT_TIME) {
+        let run_start = Instant::now();
+        let output = func();
+        samples.push(run_start.elapsed().as_nanos() as f64);
+        drop(output);
+    }
+    Stats::from_samples(samples)
+}
+
+/// Benchmarks one phase of a solution, prints the statistics and how they compare to the
+/// baseline, then records them in the baseline. Returns whether this was a regression.
+pub fn bench<F, R>(year: u32, day: u32, phase: Phase, func: F, baseline: &mut Baseline) -> bool
+    where F: FnMut() -> R
+{
+    let stats = measure(func);
+    println!(
+        "Benchmark {} day {}, {}: median {} (min {}, mean {}, stddev {}, {} samples)",
+        year, day, phase,
+        format_nanos(stats.median), format_nanos(stats.min), format_nanos(stats.mean),
+        format_nanos(stats.stddev), stats.samples);
+
+    let mut is_regression = false;
+    if let Some(prev) = baseline.get(year, day, phase) {
+        let change = compare(&prev, &stats);
+        println!(
+            "    baseline median {} ({:+.1}%){}",
+            format_nanos(prev.median),
+            100.0 * (stats.median - prev.median) / prev.median,
+            match change {
+                Change::Regression => ": REGRESSION",
+                Change::Improvement => ": improvement",
+                Change::Insignificant => "",
+            });
+        is_regression = change == Change::Regression;
+    }
+    baseline.set(year, day, phase, &stats);
+    is_regression
+}
+
+/// Formats a duration given in nanoseconds with a suitable unit and nanosecond resolution.
+pub fn format_nanos(nanos: f64) -> String {
+    if nanos >= 1e9 {
+        format!("{:.9} s", nanos / 1e9)
+    } else if nanos >= 1e6 {
+        format!("{:.6} ms", nanos / 1e6)
+    } else if nanos >= 1e3 {
+        format!("{:.3} µs", nanos / 1e3)
+    } else {
+        format!("{:.0} ns", nanos)
+    }
+}
+
+#[test]
+fn test_format_nanos() {
+    assert_eq!(format_nanos(12.0), "12 ns");
+    assert_eq!(format_nanos(1234.0), "1.234 µs");
+    assert_eq!(format_nanos(1234567.0), "1.234567 ms");
+    assert_eq!(format_nanos(2345678901.0), "2.345678901 s");
+}
+
+/// The exponent `k` of the power law `time ∝ size^k` that best fits the points, on a log-log
+/// scale. `None` if there are fewer than two distinct sizes.
+fn growth_exponent(points: &[(usize, f64)]) -> Option<f64> {
+    let logs = points.iter().map(|&(size, time)| ((size as f64).ln(), time.ln())).collect::<Vec<_>>();
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|&(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|&(_, y)| y).sum::<f64>() / n;
+    let covariance = logs.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum::<f64>();
+    let variance = logs.iter().map(|&(x, _)| (x - mean_x) * (x - mean_x)).sum::<f64>();
+    if variance > 0.0 { Some(covariance / variance) } else { None }
+}
+
+#[test]
+fn test_growth_exponent() {
+    let exponent = growth_exponent(&[(10, 300.0), (20, 1200.0), (40, 4800.0)]).unwrap();
+    assert!((exponent - 2.0).abs() < 1e-9);
+    assert_eq!(growth_exponent(&[(10, 300.0)]), None);
+}
+
+/// Formats a time-versus-size table, with a column of median times for each phase and a row for
+/// each size, followed by the growth exponent of each phase. `medians[i][j]` is the median of
+/// phase `j` at size `i`, or `None` if that phase failed.
+pub fn format_scaling(sizes: &[usize], phases: &[Phase], medians: &[Vec<Option<f64>>]) -> String {
+    let header = iter::once("size".to_string()).chain(phases.iter().map(Phase::to_string)).collect::<Vec<_>>();
+    let rows = sizes.iter().zip(medians)
+        .map(|(size, medians)| {
+            iter::once(size.to_string())
+                .chain(medians.iter().map(|median| median.map_or_else(|| "error".to_string(), format_nanos)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let widths = (0..header.len())
+        .map(|i| iter::once(&header).chain(&rows).map(|row| row[i].chars().count()).max().unwrap())
+        .collect::<Vec<_>>();
+    let format_row = |row: &[String]| {
+        row.iter().zip(&widths).map(|(cell, &width)| format!("{:>w$}", cell, w = width)).collect::<Vec<_>>().join("  ") + "\n"
+    };
+
+    let mut out = format_row(&header);
+    for row in &rows {
+        out += &format_row(row);
+    }
+    let exponents = phases.iter().enumerate()
+        .map(|(j, phase)| {
+            let points = sizes.iter().zip(medians).filter_map(|(&size, medians)| Some((size, medians[j]?))).collect::<Vec<_>>();
+            match growth_exponent(&points) {
+                Some(exponent) => format!("{} ~ size^{:.2}", phase, exponent),
+                None => format!("{} unknown", phase),
+            }
+        })
+        .collect::<Vec<_>>();
+    out + &format!("Growth: {}\n", exponents.join(", "))
+}
+
+#[test]
+fn test_format_scaling() {
+    let phases = [Phase::Parse, Phase::Part(1)];
+    let medians = vec![vec![Some(1000.0), Some(5.0e6)], vec![Some(2000.0), Some(2.0e7)], vec![Some(4000.0), None]];
+    assert_eq!(format_scaling(&[10, 20, 40], &phases, &medians), "\
+size     parse        part 1
+  10  1.000 µs   5.000000 ms
+  20  2.000 µs  20.000000 ms
+  40  4.000 µs         error
+Growth: parse ~ size^1.00, part 1 ~ size^2.00
+");
+}
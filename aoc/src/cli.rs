@@ -0,0 +1,766 @@
+//! Command line interface of the runner binary.
+
+use crate::{
+    answers, base_url, bench, fetch_and_write_input, fetch_and_write_puzzle, fetch_leaderboard,
+    input, input_file_name, invalidate, leaderboard, load_session_cookie, puzzle,
+    puzzle_description, random, run, run_parse, scaffold, session, solve, submit, summary,
+    try_example, try_input, unlock, wait_and_fetch, watch, write_input, write_puzzle, Phase,
+    Registry,
+};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::iter;
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Instant;
+
+pub const USAGE: &str = "\
+Usage: aoc [options] <days>...
+       aoc record <day> <part> <answer>
+       aoc submit <day> <part> [<answer>]
+       aoc fetch <days>...
+       aoc invalidate <days>...
+       aoc session
+       aoc wait [<day>]
+       aoc puzzle <day>
+       aoc leaderboard <id>
+       aoc summary [--json] [--threads <n>] [--part <part>] [<days>...]
+       aoc generate <day> <size> [--seed <n>]
+       aoc new <day>
+       aoc watch <day> [--part <part>]
+
+Days can be given as a single day (7), an inclusive range (1-25), or all. Each
+answer is checked against the recorded answers and marked OK, MISMATCH or
+UNKNOWN. Answers for other inputs than the real one are not checked.
+
+Commands:
+    record             Record a confirmed answer to check future runs against
+    submit             Submit an answer (computed if not given) to the website,
+                       and record it if it is correct
+    fetch              Download the input, description and examples again,
+                       replacing the cached files
+    invalidate         Delete the cached input, description and examples, so
+                       that they are downloaded again when next needed
+    session            Show where the session cookie comes from and check that
+                       it is still valid
+    wait               Wait until the puzzle unlocks (by default, the next one),
+                       then fetch its input, description and examples right away
+    puzzle             Print the puzzle description as Markdown
+    leaderboard        Show the standings of a private leaderboard, with the
+                       time each member took for each star
+    summary            Run the days (by default, all of them) in parallel and
+                       print a table of the answers, times and verification
+                       results; days that take over a second are marked.
+                       --json prints the results as JSON instead, and
+                       --threads sets the number of threads (by default, one
+                       per CPU), and --part runs only the given part
+    generate           Print a random input of the given size (for example, the
+                       number of tiles along each side of the day 20 jigsaw),
+                       for days that have a generator
+    new                Create a skeleton solution in src/dayDD.rs, register
+                       it in src/lib.rs, and fetch the input, description and
+                       examples; an existing solution is never overwritten
+    watch              Rebuild and rerun the day, and run its tests, whenever
+                       its source, input or examples change; each run shows
+                       the answers that changed and the times compared to the
+                       previous run
+
+Environment:
+    AOC_SESSION        Session cookie to use instead of the configured one
+    AOC_PROFILE        Named profile to take the session cookie from
+    AOC_CONFIG_DIR     Directory of the sessions config file
+    AOC_BASE_URL       Website to use instead of https://adventofcode.com
+
+Options:
+    --part <part>      Run only part 1 or part 2
+    --input <file>     Read the input from the given file (single day only)
+    --stdin            Read the input from standard input (single day only)
+    --example <index>  Use the example with the given index as the input
+    --bench            Run each part many times on the real input and report
+                       timing statistics
+    --save-baseline    Like --bench, but also save the results as the new baseline
+    --sizes <sizes>    Like --bench, but on generated inputs of each of the given
+                       comma-separated sizes instead of the real input, and
+                       print how the time grows with the size
+    --seed <n>         Seed for the generated inputs (default 0)";
+
+pub fn main(registry: &Registry) {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("record") => record(&args[1..], registry),
+        Some("submit") => submit(&args[1..], registry),
+        Some("fetch") => fetch(&args[1..], registry),
+        Some("invalidate") => invalidate_days(&args[1..], registry),
+        Some("session") => check_session(),
+        Some("wait") => wait(&args[1..], registry),
+        Some("puzzle") => show_puzzle(&args[1..], registry),
+        Some("leaderboard") => show_leaderboard(&args[1..], registry),
+        Some("summary") => show_summary(&args[1..], registry),
+        Some("generate") => generate(&args[1..], registry),
+        Some("new") => new_day(&args[1..], registry),
+        Some("watch") => watch_day(&args[1..], registry),
+        _ => run_days(&args, registry),
+    }
+}
+
+fn usage_error(err: &str) -> ! {
+    eprintln!("{}\n\n{}", err, USAGE);
+    process::exit(2);
+}
+
+fn parse_day(s: &str) -> Result<u32, String> {
+    match s.parse::<u32>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("Invalid day: {}", s)),
+    }
+}
+
+fn parse_part(s: &str) -> Result<u32, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("Invalid part: {}", s)),
+    }
+}
+
+/// Where to read a day's input from.
+#[derive(Debug, PartialEq)]
+enum Source {
+    Real,
+    File(String),
+    Stdin,
+    Example(usize),
+}
+
+impl Source {
+    fn read(&self, year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+        match self {
+            Source::Real => try_input(year, day),
+            Source::File(file_name) => Ok(fs::read_to_string(file_name)?),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            },
+            Source::Example(index) => try_example(year, day, *index),
+        }
+    }
+}
+
+/// Parses a single day (7), an inclusive range (1-25), or all registered days.
+fn parse_days(arg: &str, registry: &Registry) -> Result<Vec<u32>, String> {
+    if arg == "all" {
+        Ok(registry.days().collect())
+    } else if let Some(dash) = arg.find('-') {
+        let first = parse_day(&arg[..dash])?;
+        let last = parse_day(&arg[dash + 1..])?;
+        if first > last {
+            return Err(format!("Invalid range: {}", arg));
+        }
+        Ok((first..=last).collect())
+    } else {
+        Ok(vec![parse_day(arg)?])
+    }
+}
+
+#[derive(Debug)]
+struct RunArgs {
+    days: Vec<u32>,
+    parts: Vec<u32>,
+    source: Source,
+    bench: bool,
+    save_baseline: bool,
+    sizes: Option<Vec<usize>>,
+    seed: u64,
+}
+
+impl RunArgs {
+    fn parse(args: &[String], registry: &Registry) -> Result<RunArgs, String> {
+        let mut parsed = RunArgs {
+            days: Vec::new(),
+            parts: vec![1, 2],
+            source: Source::Real,
+            bench: false,
+            save_baseline: false,
+            sizes: None,
+            seed: 0,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("Missing value for {}", arg));
+            let mut set_source = |source| match parsed.source {
+                Source::Real => { parsed.source = source; Ok(()) },
+                _ => Err("Only one of --input, --stdin and --example can be given".to_string()),
+            };
+            if arg == "--part" {
+                parsed.parts = vec![parse_part(value()?)?];
+            } else if arg == "--input" {
+                let file_name = value()?.clone();
+                set_source(Source::File(file_name))?;
+            } else if arg == "--stdin" {
+                set_source(Source::Stdin)?;
+            } else if arg == "--example" {
+                let index = value()?;
+                let index = index.parse().map_err(|_| format!("Invalid example index: {}", index))?;
+                set_source(Source::Example(index))?;
+            } else if arg == "--bench" {
+                parsed.bench = true;
+            } else if arg == "--save-baseline" {
+                parsed.bench = true;
+                parsed.save_baseline = true;
+            } else if arg == "--sizes" {
+                let sizes = value()?;
+                let sizes = sizes.split(',')
+                    .map(|size| size.parse().ok().filter(|&size| size > 0))
+                    .collect::<Option<Vec<usize>>>()
+                    .ok_or_else(|| format!("Invalid sizes: {}", sizes))?;
+                parsed.bench = true;
+                parsed.sizes = Some(sizes);
+            } else if arg == "--seed" {
+                let seed = value()?;
+                parsed.seed = seed.parse().map_err(|_| format!("Invalid seed: {}", seed))?;
+            } else if arg.starts_with("--") {
+                return Err(format!("Unknown option: {}", arg));
+            } else {
+                parsed.days.extend(parse_days(arg, registry)?);
+            }
+        }
+        if parsed.days.is_empty() {
+            return Err("No days given".to_string());
+        }
+        if parsed.days.len() > 1 && matches!(parsed.source, Source::File(_) | Source::Stdin) {
+            return Err("--input and --stdin can only be used with a single day".to_string());
+        }
+        if parsed.sizes.is_some() && (parsed.save_baseline || parsed.source != Source::Real) {
+            return Err("--sizes generates its own inputs, and does not save a baseline".to_string());
+        }
+        // The baseline was measured on the real input, so other inputs can't be compared to it.
+        if parsed.bench && parsed.source != Source::Real {
+            return Err("--bench and --save-baseline can only be used with the real input".to_string());
+        }
+        Ok(parsed)
+    }
+}
+
+#[test]
+fn test_run_args_parse() {
+    let registry = Registry::new(2020);
+    let parse = |args: &[&str]| RunArgs::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>(), &registry);
+
+    let args = parse(&["7"]).unwrap();
+    assert_eq!((args.days, args.parts, args.source), (vec![7], vec![1, 2], Source::Real));
+    let args = parse(&["--part", "2", "7", "--input", "other.txt"]).unwrap();
+    assert_eq!((args.days, args.parts, args.source), (vec![7], vec![2], Source::File("other.txt".to_string())));
+    let args = parse(&["1-3", "--example", "1"]).unwrap();
+    assert_eq!((args.days, args.source), (vec![1, 2, 3], Source::Example(1)));
+    assert_eq!(parse(&["7-7"]).unwrap().days, vec![7]);
+    assert_eq!(parse(&["7", "--stdin"]).unwrap().source, Source::Stdin);
+
+    assert_eq!(parse(&["25-1"]).unwrap_err(), "Invalid range: 25-1");
+    assert!(parse(&["7", "--part"]).is_err());
+    assert!(parse(&["7", "--part", "3"]).is_err());
+    assert!(parse(&["7", "--example", "x"]).is_err());
+    assert!(parse(&["7", "--stdin", "--example", "0"]).is_err());
+    assert!(parse(&["1-3", "--input", "other.txt"]).is_err());
+    assert!(parse(&["7", "--example", "0", "--save-baseline"]).is_err());
+    assert!(parse(&["7", "--bench"]).unwrap().bench);
+    assert!(parse(&["7", "--example", "0", "--bench"]).is_err());
+    assert!(parse(&["7", "--bench", "--input", "other.txt"]).is_err());
+    assert!(parse(&["7", "--stdin", "--bench"]).is_err());
+
+    let args = parse(&["20", "--sizes", "3,6,12", "--seed", "42"]).unwrap();
+    assert_eq!((args.bench, args.sizes, args.seed), (true, Some(vec![3, 6, 12]), 42));
+    assert!(parse(&["20", "--sizes", "3,x"]).is_err());
+    assert!(parse(&["20", "--sizes", "0"]).is_err());
+    assert!(parse(&["20", "--sizes", "3", "--example", "0"]).is_err());
+}
+
+fn run_days(args: &[String], registry: &Registry) {
+    let args = RunArgs::parse(args, registry).unwrap_or_else(|err| usage_error(&err));
+    if let Some(&day) = args.days.iter().find(|&&day| registry.get(day).is_none()) {
+        eprintln!("No solution registered for day {}", day);
+        process::exit(1);
+    }
+
+    if let Some(sizes) = &args.sizes {
+        let num_failed = args.days.iter()
+            .filter(|&&day| !bench_sizes(registry, day, &args.parts, sizes, args.seed))
+            .count();
+        if num_failed > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let year = registry.year();
+    let mut baseline = if args.bench {
+        Some(bench::Baseline::load().unwrap_or_else(|err| {
+            eprintln!("Could not load baseline {}: {}", bench::BASELINE_FILE_NAME, err);
+            process::exit(1);
+        }))
+    } else {
+        None
+    };
+    let mut num_regressions = 0;
+    let mut num_mismatches = 0;
+    let mut num_errors = 0;
+    for &day in &args.days {
+        let parse = registry.get(day).unwrap();
+        let input = args.source.read(year, day).unwrap_or_else(|err| {
+            eprintln!("Could not read input for day {}: {}", day, err);
+            process::exit(1);
+        });
+        let verify = args.source == Source::Real;
+        let parsed = match run_parse(year, day, parse, &input) {
+            Some(parsed) => parsed,
+            None => {
+                num_errors += 1;
+                continue;
+            },
+        };
+        if let Some(baseline) = baseline.as_mut() {
+            if bench::bench(year, day, Phase::Parse, || parse(&input), baseline) {
+                num_regressions += 1;
+            }
+        }
+        for &part in &args.parts {
+            match baseline.as_mut() {
+                Some(baseline) => {
+                    if let Err(err) = parsed.run(part) {
+                        eprintln!("Error in {} day {}, {}: {}", year, day, Phase::Part(part), err);
+                        num_errors += 1;
+                    } else if bench::bench(year, day, Phase::Part(part), || parsed.run(part), baseline) {
+                        num_regressions += 1;
+                    }
+                },
+                None => {
+                    match run(year, day, part, verify, || parsed.run(part)) {
+                        Some(answers::Verdict::Mismatch { .. }) => num_mismatches += 1,
+                        Some(_) => {},
+                        None => num_errors += 1,
+                    }
+                },
+            }
+        }
+    }
+
+    if args.save_baseline {
+        if let Err(err) = baseline.unwrap().save() {
+            eprintln!("Could not save baseline {}: {}", bench::BASELINE_FILE_NAME, err);
+            process::exit(1);
+        }
+        println!("Saved baseline to {}", bench::BASELINE_FILE_NAME);
+    }
+    if num_mismatches > 0 {
+        println!("{} answer(s) did not match the recorded answer", num_mismatches);
+    }
+    if num_regressions > 0 {
+        println!("{} regression(s) against baseline", num_regressions);
+    }
+    if num_errors > 0 {
+        println!("{} part(s) failed", num_errors);
+    }
+    if num_mismatches > 0 || num_regressions > 0 || num_errors > 0 {
+        process::exit(1);
+    }
+}
+
+/// Benchmarks a day on a generated input of each size, then prints how the time grows with the
+/// size. Returns whether all phases succeeded.
+fn bench_sizes(registry: &Registry, day: u32, parts: &[u32], sizes: &[usize], seed: u64) -> bool {
+    let year = registry.year();
+    let parse = registry.get(day).unwrap();
+    let generate = registry.generator(day).unwrap();
+    let phases = iter::once(Phase::Parse).chain(parts.iter().map(|&part| Phase::Part(part))).collect::<Vec<_>>();
+    let mut medians = Vec::new();
+    for &size in sizes {
+        let input = match generate(&mut random::Rng::new(seed), size) {
+            Some(input) => input,
+            None => {
+                eprintln!("No input generator for day {}", day);
+                return false;
+            },
+        };
+        println!("Benchmarking {} day {} on a generated input of size {} ({} bytes)", year, day, size, input.len());
+        let mut size_medians = vec![None; phases.len()];
+        match parse(&input) {
+            Ok(parsed) => {
+                size_medians[0] = Some(bench::measure(|| parse(&input)).median);
+                for (i, &part) in parts.iter().enumerate() {
+                    match parsed.run(part) {
+                        Ok(_) => size_medians[i + 1] = Some(bench::measure(|| parsed.run(part)).median),
+                        Err(err) => eprintln!("Error in {} day {}, {}: {}", year, day, Phase::Part(part), err),
+                    }
+                }
+            },
+            Err(err) => eprintln!("Error in {} day {}, {}: {}", year, day, Phase::Parse, err),
+        }
+        medians.push(size_medians);
+    }
+    println!("Time versus size for {} day {} (seed {}):", year, day, seed);
+    print!("{}", bench::format_scaling(sizes, &phases, &medians));
+    medians.iter().flatten().all(Option::is_some)
+}
+
+fn record(args: &[String], registry: &Registry) {
+    let (day, part, answer) = match args {
+        [day, part, answer] => (
+            parse_day(day).unwrap_or_else(|err| usage_error(&err)),
+            parse_part(part).unwrap_or_else(|err| usage_error(&err)),
+            answer),
+        _ => usage_error("Expected <day> <part> <answer>"),
+    };
+    let year = registry.year();
+    if let Err(err) = answers::record(year, day, part, answer) {
+        eprintln!("Could not record answer: {}", err);
+        process::exit(1);
+    }
+    println!(
+        "Recorded answer {} for {} day {}, part {} in {}",
+        answer.trim(), year, day, part, answers::answer_file_name(year, day, part));
+}
+
+fn submit(args: &[String], registry: &Registry) {
+    let (day, part, answer) = match args {
+        [day, part] | [day, part, _] => (
+            parse_day(day).unwrap_or_else(|err| usage_error(&err)),
+            parse_part(part).unwrap_or_else(|err| usage_error(&err)),
+            args.get(2).map(|answer| answer.trim().to_string())),
+        _ => usage_error("Expected <day> <part> [<answer>]"),
+    };
+    let year = registry.year();
+    let answer = answer.unwrap_or_else(|| {
+        let parse = registry.get(day).unwrap_or_else(|| {
+            eprintln!("No solution registered for day {}", day);
+            process::exit(1);
+        });
+        solve(parse, &input(year, day), part).unwrap_or_else(|err| {
+            eprintln!("Error in {} day {}, part {}: {}", year, day, part, err);
+            process::exit(1);
+        })
+    });
+    if let Some(known) = answers::load(year, day, part) {
+        eprintln!("Not submitting {}: the answer to {} day {}, part {} is already known to be {}", answer, year, day, part, known);
+        process::exit(1);
+    }
+
+    let log_file_name = submit::attempt_log_file_name(year, day, part);
+    let mut log = submit::AttemptLog::load(&log_file_name).unwrap_or_else(|err| {
+        eprintln!("Could not load attempt log {}: {}", log_file_name, err);
+        process::exit(1);
+    });
+    let outcome = load_session_cookie()
+        .and_then(|session_cookie| submit::submit(&base_url(), &session_cookie, year, day, part, &answer, &mut log))
+        .unwrap_or_else(|err| {
+            eprintln!("Could not submit answer {}: {}", answer, err);
+            process::exit(1);
+        });
+    println!("Submitted answer {} for {} day {}, part {}: {}", answer, year, day, part, outcome);
+    if outcome != submit::Outcome::Correct {
+        process::exit(1);
+    }
+    if let Err(err) = answers::record(year, day, part, &answer) {
+        // The website won't take the answer again, so make sure it is not lost.
+        eprintln!("Could not record the correct answer {}: {}", answer, err);
+        eprintln!("Record it later with: aoc record {} {} {}", day, part, answer);
+        process::exit(1);
+    }
+    println!("Recorded answer in {}", answers::answer_file_name(year, day, part));
+    if part == 1 {
+        match fetch_and_write_puzzle(year, day) {
+            Ok(_) => println!("Fetched part two into {}", puzzle::file_name(year, day)),
+            Err(err) => eprintln!("Could not fetch part two: {}", err),
+        }
+    }
+}
+
+fn parse_days_args(args: &[String], registry: &Registry) -> Vec<u32> {
+    if args.is_empty() {
+        usage_error("No days given");
+    }
+    args.iter()
+        .map(|arg| parse_days(arg, registry))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|err| usage_error(&err))
+        .concat()
+}
+
+fn fetch(args: &[String], registry: &Registry) {
+    let year = registry.year();
+    let num_errors = parse_days_args(args, registry).into_iter().map(|day| fetch_day(year, day)).sum::<usize>();
+    if num_errors > 0 {
+        process::exit(1);
+    }
+}
+
+/// Fetches the input, description and examples for one day, and returns the number of errors.
+fn fetch_day(year: u32, day: u32) -> usize {
+    let mut num_errors = 0;
+    match fetch_and_write_input(year, day) {
+        Ok(_) => println!("Fetched input for {} day {} into {}", year, day, input_file_name(year, day)),
+        Err(err) => {
+            eprintln!("Could not fetch input for {} day {}: {}", year, day, err);
+            num_errors += 1;
+        },
+    }
+    match fetch_and_write_puzzle(year, day) {
+        Ok(puzzle) => println!(
+            "Fetched the description into {} and {} example(s) for {} day {}",
+            puzzle::file_name(year, day), puzzle.examples.len(), year, day),
+        Err(err) => {
+            eprintln!("Could not fetch examples for {} day {}: {}", year, day, err);
+            num_errors += 1;
+        },
+    }
+    num_errors
+}
+
+fn new_day(args: &[String], registry: &Registry) {
+    let year = registry.year();
+    let day = match args {
+        [day] => parse_day(day).unwrap_or_else(|err| usage_error(&err)),
+        _ => usage_error("Expected a single day"),
+    };
+    if registry.get(day).is_some() {
+        eprintln!("{} day {} already has a solution", year, day);
+        process::exit(1);
+    }
+    let file_name = scaffold::add_day(Path::new("."), day).unwrap_or_else(|err| {
+        eprintln!("Could not create a solution for {} day {}: {}", year, day, err);
+        process::exit(1);
+    });
+    println!("Created {} and registered it in src/lib.rs", file_name);
+    if fetch_day(year, day) > 0 {
+        process::exit(1);
+    }
+}
+
+fn invalidate_days(args: &[String], registry: &Registry) {
+    let year = registry.year();
+    for day in parse_days_args(args, registry) {
+        let deleted = invalidate(year, day).unwrap_or_else(|err| {
+            eprintln!("Could not invalidate {} day {}: {}", year, day, err);
+            process::exit(1);
+        });
+        for file_name in deleted {
+            println!("Deleted {}", file_name);
+        }
+    }
+}
+
+fn check_session() {
+    let session = session::load().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    println!("Using session cookie for profile {} from {}", session.profile, session.source);
+    match session::check(&base_url(), &session.cookie) {
+        Ok(()) => println!("Session cookie is valid"),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        },
+    }
+}
+
+fn wait(args: &[String], registry: &Registry) {
+    let year = registry.year();
+    let mut clock = unlock::SystemClock;
+    let day = match args {
+        [] => unlock::next_day(&clock, year).unwrap_or_else(|| {
+            eprintln!("All puzzles of {} have already unlocked", year);
+            process::exit(1);
+        }),
+        [day] => parse_day(day).unwrap_or_else(|err| usage_error(&err)),
+        _ => usage_error("Expected at most one day"),
+    };
+    let fail = |err: Box<dyn Error>| -> ! {
+        eprintln!("Could not fetch {} day {}: {}", year, day, err);
+        process::exit(1);
+    };
+    let session_cookie = load_session_cookie().unwrap_or_else(|err| fail(err));
+    println!("Waiting for {} day {} to unlock", year, day);
+    let (input, puzzle) = wait_and_fetch(&mut clock, &base_url(), &session_cookie, year, day)
+        .unwrap_or_else(|err| fail(err));
+    write_input(year, day, &input).unwrap_or_else(|err| fail(err.into()));
+    write_puzzle(year, day, &puzzle).unwrap_or_else(|err| fail(err.into()));
+    println!(
+        "Fetched input into {}, the description into {} and {} example(s)",
+        input_file_name(year, day), puzzle::file_name(year, day), puzzle.examples.len());
+}
+
+fn show_leaderboard(args: &[String], registry: &Registry) {
+    let year = registry.year();
+    let id = match args {
+        [id] if id.chars().all(|c| c.is_ascii_digit()) => id,
+        [id] => usage_error(&format!("Invalid leaderboard id: {}", id)),
+        _ => usage_error("Expected a leaderboard id"),
+    };
+    let cache_file_name = leaderboard::cache_file_name(year, id);
+    let fetch = || fetch_leaderboard(&base_url(), &load_session_cookie()?, year, id);
+    let (leaderboard, age) = leaderboard::load(&unlock::SystemClock, Path::new(&cache_file_name), fetch)
+        .unwrap_or_else(|err| {
+            eprintln!("Could not load leaderboard {} for {}: {}", id, year, err);
+            process::exit(1);
+        });
+    println!("Private leaderboard {} for {}, as of {} minute(s) ago", id, year, age.as_secs() / 60);
+    print!("{}", leaderboard.report());
+}
+
+fn show_puzzle(args: &[String], registry: &Registry) {
+    let year = registry.year();
+    let day = match args {
+        [day] => parse_day(day).unwrap_or_else(|err| usage_error(&err)),
+        _ => usage_error("Expected a single day"),
+    };
+    match puzzle_description(year, day) {
+        Ok(description) => print!("{}", description),
+        Err(err) => {
+            eprintln!("Could not get the description of {} day {}: {}", year, day, err);
+            process::exit(1);
+        },
+    }
+}
+
+fn show_summary(args: &[String], registry: &Registry) {
+    let mut json = false;
+    let mut threads = num_cpus::get();
+    let mut parts = vec![1, 2];
+    let mut day_args = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--threads" => {
+                threads = args.next()
+                    .and_then(|arg| arg.parse().ok())
+                    .filter(|&threads| threads > 0)
+                    .unwrap_or_else(|| usage_error("--threads requires a positive number"));
+            },
+            "--part" => {
+                let part = args.next().unwrap_or_else(|| usage_error("--part requires a part"));
+                parts = vec![parse_part(part).unwrap_or_else(|err| usage_error(&err))];
+            },
+            _ => day_args.push(arg.clone()),
+        }
+    }
+    let days = if day_args.is_empty() { registry.days().collect() } else { parse_days_args(&day_args, registry) };
+    if let Some(&day) = days.iter().find(|&&day| registry.get(day).is_none()) {
+        eprintln!("No solution registered for day {}", day);
+        process::exit(1);
+    }
+
+    // Read the inputs up front, so that any downloads are not part of the timing.
+    let year = registry.year();
+    let jobs = days.iter()
+        .map(|&day| {
+            let input = try_input(year, day).unwrap_or_else(|err| {
+                eprintln!("Could not read input for day {}: {}", day, err);
+                process::exit(1);
+            });
+            (day, registry.get(day).unwrap(), input)
+        })
+        .collect();
+    let start = Instant::now();
+    let outcomes = summary::run_parallel(year, jobs, &parts, threads);
+    let wall_time = start.elapsed();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&summary::to_json(year, &outcomes, wall_time)).unwrap());
+    } else {
+        print!("{}", summary::table(&outcomes, wall_time));
+    }
+    let failed = outcomes.iter()
+        .flat_map(|outcome| &outcome.phases)
+        .any(|phase| phase.result.is_err() || matches!(phase.verdict, Some(answers::Verdict::Mismatch { .. })));
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn generate(args: &[String], registry: &Registry) {
+    let mut seed = 0;
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--seed" {
+            seed = args.next()
+                .and_then(|seed| seed.parse().ok())
+                .unwrap_or_else(|| usage_error("--seed requires a number"));
+        } else {
+            positional.push(arg);
+        }
+    }
+    let (day, size) = match positional.as_slice() {
+        [day, size] => (
+            parse_day(day).unwrap_or_else(|err| usage_error(&err)),
+            size.parse().ok().filter(|&size| size > 0).unwrap_or_else(|| usage_error(&format!("Invalid size: {}", size))),
+        ),
+        _ => usage_error("Expected <day> <size>"),
+    };
+    let generate = registry.generator(day).unwrap_or_else(|| {
+        eprintln!("No solution registered for day {}", day);
+        process::exit(1);
+    });
+    match generate(&mut random::Rng::new(seed), size) {
+        Some(input) => print!("{}", input),
+        None => {
+            eprintln!("No input generator for day {}", day);
+            process::exit(1);
+        },
+    }
+}
+
+fn watch_day(args: &[String], registry: &Registry) {
+    let year = registry.year();
+    let mut day = None;
+    let mut parts = vec![1, 2];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--part" {
+            let part = args.next().unwrap_or_else(|| usage_error("--part requires a part"));
+            parts = vec![parse_part(part).unwrap_or_else(|err| usage_error(&err))];
+        } else if day.is_none() {
+            day = Some(parse_day(arg).unwrap_or_else(|err| usage_error(&err)));
+        } else {
+            usage_error("Expected a single day");
+        }
+    }
+    let day = day.unwrap_or_else(|| usage_error("No day given"));
+    let watched_files = || watch::watched_files(year, day).unwrap_or_else(|err| {
+        eprintln!("Could not list the files of {} day {}: {}", year, day, err);
+        process::exit(1);
+    });
+
+    let mut previous = Vec::new();
+    let mut changed = Vec::new();
+    loop {
+        // Taken before running, so that changes made during the run cause another one.
+        let file_names = watched_files();
+        let snapshot = watch::snapshot(&file_names);
+        if changed.is_empty() {
+            println!("Running {} day {}", year, day);
+        } else {
+            println!("\nChanged {}, running {} day {} again", changed.join(", "), year, day);
+        }
+        match watch::run_parts(day, &parts) {
+            Ok(results) => {
+                print!("{}", watch::compare(&previous, &results));
+                previous = results;
+            },
+            Err(output) => println!("{}", output),
+        }
+        print!("{}", watch::run_tests(day));
+        println!("Watching {} for changes...", file_names.join(", "));
+        changed = loop {
+            thread::sleep(watch::POLL_INTERVAL);
+            let changed = watch::changed_files(&snapshot, &watch::snapshot(&watched_files()));
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+    }
+}
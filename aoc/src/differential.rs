@@ -0,0 +1,233 @@
+//! Differential testing: runs an optimized routine against a naive reference implementation on
+//! randomly generated cases.
+//!
+//! When the two disagree (or either panics), the case is shrunk to a minimal one that still makes
+//! them disagree, and it is saved as puzzle input in `regressions/YYYY/DD-NAME-N.in`. Saved cases
+//! are checked first on every later run, so a bug that was once found stays covered after the
+//! generator moves on.
+//!
+//! The generated cases are reproducible: the seed is 0 unless it is overridden with the
+//! `AOC_SEED` environment variable, and it is included in failure messages.
+
+use crate::random::Rng;
+use crate::ParseError;
+use std::env;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+/// An optimized routine, paired with a reference implementation and a generator of cases. Both
+/// implementations must finish on every case that `generate` and `shrink` return, since a case
+/// that makes one of them loop forever can't be detected.
+pub trait Differential {
+    const YEAR: u32;
+    const DAY: u32;
+    /// Distinguishes the regression files of the tests for one day.
+    const NAME: &'static str;
+
+    type Case: Clone + Debug;
+    type Output: PartialEq + Debug;
+
+    fn generate(rng: &mut Rng) -> Self::Case;
+
+    /// Cases that are smaller than the given one, in the order they should be tried. They must be
+    /// valid input for both implementations.
+    fn shrink(case: &Self::Case) -> Vec<Self::Case>;
+
+    fn optimized(case: &Self::Case) -> Self::Output;
+    fn reference(case: &Self::Case) -> Self::Output;
+
+    /// Formats the case as puzzle input, to save it as a regression.
+    fn to_input(case: &Self::Case) -> String;
+    /// Parses a saved regression.
+    fn from_input(input: &str) -> Result<Self::Case, ParseError>;
+}
+
+/// Smaller versions of a list: with each element removed in turn, starting with the last. Useful
+/// to implement `Differential::shrink`.
+pub fn shrink_vec<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    (0..items.len()).rev()
+        .map(|i| items[..i].iter().chain(&items[i + 1..]).cloned().collect())
+        .collect()
+}
+
+#[test]
+fn test_shrink_vec() {
+    assert_eq!(shrink_vec(&[1, 2, 3]), vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+    assert!(shrink_vec::<u8>(&[]).is_empty());
+}
+
+/// The outputs of both implementations on a case where they disagree. A panic is recorded as
+/// its message.
+#[derive(Debug)]
+struct Mismatch<O> {
+    optimized: Result<O, String>,
+    reference: Result<O, String>,
+}
+
+fn run<F, O>(func: F) -> Result<O, String>
+    where F: FnOnce() -> O
+{
+    panic::catch_unwind(AssertUnwindSafe(func)).map_err(|payload| {
+        payload.downcast_ref::<String>().cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|message| message.to_string()))
+            .unwrap_or_else(|| "panicked".to_string())
+    })
+}
+
+fn compare<D: Differential>(case: &D::Case) -> Option<Mismatch<D::Output>> {
+    let optimized = run(|| D::optimized(case));
+    let reference = run(|| D::reference(case));
+    match (&optimized, &reference) {
+        (Ok(a), Ok(b)) if a == b => None,
+        _ => Some(Mismatch { optimized, reference }),
+    }
+}
+
+/// Shrinks a failing case, by repeatedly moving to the first smaller case that still fails.
+fn shrink<D: Differential>(mut case: D::Case, mut mismatch: Mismatch<D::Output>) -> (D::Case, Mismatch<D::Output>) {
+    'outer: loop {
+        for smaller in D::shrink(&case) {
+            if let Some(smaller_mismatch) = compare::<D>(&smaller) {
+                case = smaller;
+                mismatch = smaller_mismatch;
+                continue 'outer;
+            }
+        }
+        return (case, mismatch);
+    }
+}
+
+fn regressions_dir(year: u32) -> String {
+    format!("regressions/{}", year)
+}
+
+fn regression_file_names<D: Differential>(dir: &Path) -> io::Result<Vec<String>> {
+    let prefix = format!("{:02}-{}-", D::DAY, D::NAME);
+    let mut file_names = Vec::new();
+    if dir.is_dir() {
+        for entry in fs::read_dir(dir)? {
+            let file_name = entry?.file_name().to_string_lossy().into_owned();
+            let index = file_name.strip_prefix(&prefix).and_then(|rest| rest.strip_suffix(".in"));
+            if index.map_or(false, |index| index.parse::<usize>().is_ok()) {
+                file_names.push(dir.join(file_name).to_string_lossy().into_owned());
+            }
+        }
+    }
+    file_names.sort();
+    Ok(file_names)
+}
+
+/// Saves the case under the first free index, and returns the file name.
+fn save_regression<D: Differential>(dir: &Path, case: &D::Case) -> io::Result<String> {
+    fs::create_dir_all(dir)?;
+    let existing = regression_file_names::<D>(dir)?;
+    let file_name = (0..)
+        .map(|index| dir.join(format!("{:02}-{}-{}.in", D::DAY, D::NAME, index)).to_string_lossy().into_owned())
+        .find(|file_name| !existing.contains(file_name))
+        .unwrap();
+    fs::write(&file_name, D::to_input(case))?;
+    Ok(file_name)
+}
+
+fn seed() -> u64 {
+    env::var("AOC_SEED").ok()
+        .map(|seed| seed.parse().unwrap_or_else(|_| panic!("AOC_SEED must be a number, got {:?}", seed)))
+        .unwrap_or(0)
+}
+
+/// Checks the saved regressions, then `num_cases` generated cases. Panics on the first case where
+/// the implementations disagree, after shrinking and saving it.
+pub fn check<D: Differential>(num_cases: usize) {
+    check_in::<D>(Path::new(&regressions_dir(D::YEAR)), seed(), num_cases)
+}
+
+fn check_in<D: Differential>(dir: &Path, seed: u64, num_cases: usize) {
+    for file_name in regression_file_names::<D>(dir).unwrap() {
+        let input = fs::read_to_string(&file_name).unwrap();
+        let case = D::from_input(&input).unwrap_or_else(|err| panic!("{}: invalid input at {}", file_name, err));
+        if let Some(mismatch) = compare::<D>(&case) {
+            panic!("regression {} fails for {:?}: {:?}", file_name, case, mismatch);
+        }
+    }
+
+    let mut rng = Rng::new(seed);
+    for index in 0..num_cases {
+        let case = D::generate(&mut rng);
+        if let Some(mismatch) = compare::<D>(&case) {
+            let (shrunk, mismatch) = shrink::<D>(case.clone(), mismatch);
+            let file_name = save_regression::<D>(dir, &shrunk).unwrap();
+            panic!(
+                "case {} with seed {} fails for {:?}\nshrunk to {:?}: {:?}\nsaved as {}",
+                index, seed, case, shrunk, mismatch, file_name);
+        }
+    }
+}
+
+/// Sorts a list, but the "optimized" version gets lists containing both 3 and 7 wrong.
+#[cfg(test)]
+struct BrokenSort;
+
+#[cfg(test)]
+impl Differential for BrokenSort {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 1;
+    const NAME: &'static str = "sort";
+
+    type Case = Vec<u8>;
+    type Output = Vec<u8>;
+
+    fn generate(rng: &mut Rng) -> Vec<u8> {
+        (0..rng.range(0..20)).map(|_| rng.range(0..10) as u8).collect()
+    }
+
+    fn shrink(case: &Vec<u8>) -> Vec<Vec<u8>> {
+        shrink_vec(case)
+    }
+
+    fn optimized(case: &Vec<u8>) -> Vec<u8> {
+        let mut sorted = case.clone();
+        sorted.sort_unstable();
+        if sorted.contains(&3) && sorted.contains(&7) {
+            sorted.reverse();
+        }
+        sorted
+    }
+
+    fn reference(case: &Vec<u8>) -> Vec<u8> {
+        let mut sorted = case.clone();
+        sorted.sort_unstable();
+        sorted
+    }
+
+    fn to_input(case: &Vec<u8>) -> String {
+        case.iter().map(|n| format!("{}\n", n)).collect()
+    }
+
+    fn from_input(input: &str) -> Result<Vec<u8>, ParseError> {
+        crate::parse::numbers(input)
+    }
+}
+
+#[test]
+fn test_check() {
+    let dir = crate::testing::temp_dir("differential-check");
+    let result = panic::catch_unwind(|| check_in::<BrokenSort>(&dir, 1, 1000));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("shrunk to [7, 3]: "), "{}", message);
+    let file_name = dir.join("01-sort-0.in");
+    assert!(message.ends_with(&format!("saved as {}", file_name.display())), "{}", message);
+    assert_eq!(fs::read_to_string(&file_name).unwrap(), "7\n3\n");
+
+    // The saved case is checked before any new ones.
+    let result = panic::catch_unwind(|| check_in::<BrokenSort>(&dir, 1, 0));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.starts_with(&format!("regression {} fails for [7, 3]", file_name.display())), "{}", message);
+
+    fs::write(&file_name, "3\n").unwrap();
+    check_in::<BrokenSort>(&dir, 1, 0);
+    fs::write(dir.join("01-sort-1.in"), "x\n").unwrap();
+    assert!(panic::catch_unwind(|| check_in::<BrokenSort>(&dir, 1, 0)).is_err());
+}
@@ -0,0 +1,69 @@
+use std::error::Error;
+use std::fmt;
+
+/// An error in the puzzle input, at a 1-based line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError { line, column, message: message.into() }
+    }
+
+    /// Returns an error pointing at the start of `part`, which must be a slice of `input`.
+    pub fn at(input: &str, part: &str, message: impl Into<String>) -> ParseError {
+        let offset = part.as_ptr() as usize - input.as_ptr() as usize;
+        assert!(offset <= input.len(), "part is not a slice of input");
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap().chars().count() + 1;
+        ParseError::new(line, column, message)
+    }
+
+    /// Converts an error relative to `part`, which must be a slice of `input`, into one relative
+    /// to `input`.
+    pub fn within(self, input: &str, part: &str) -> ParseError {
+        let start = ParseError::at(input, part, "");
+        let column = if self.line == 1 { start.column + self.column - 1 } else { self.column };
+        ParseError::new(start.line + self.line - 1, column, self.message)
+    }
+
+    /// Moves the error down by the given number of lines. Useful when an error was created
+    /// relative to a single line of a larger input.
+    pub fn offset_lines(mut self, lines: usize) -> ParseError {
+        self.line += lines;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[test]
+fn test_parse_error_at() {
+    let input = "nop +0\nacc +1\njmp +x\n";
+    let arg = &input[18..20];
+    assert_eq!(arg, "+x");
+    assert_eq!(ParseError::at(input, arg, "bad"), ParseError::new(3, 5, "bad"));
+    assert_eq!(ParseError::at(input, input, "bad"), ParseError::new(1, 1, "bad"));
+    assert_eq!(ParseError::at(input, &input[input.len()..], "bad"), ParseError::new(4, 1, "bad"));
+    assert_eq!(ParseError::at("x", "x", "bad").offset_lines(2).to_string(), "line 3, column 1: bad");
+}
+
+#[test]
+fn test_parse_error_within() {
+    let input = "nop +0\nacc +1\njmp +x\n";
+    let line = &input[14..20];
+    assert_eq!(ParseError::new(1, 5, "bad").within(input, line), ParseError::new(3, 5, "bad"));
+    assert_eq!(ParseError::new(1, 2, "bad").within(input, &line[4..]), ParseError::new(3, 6, "bad"));
+    assert_eq!(ParseError::new(2, 3, "bad").within(input, &input[4..]), ParseError::new(2, 3, "bad"));
+}
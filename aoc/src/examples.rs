@@ -0,0 +1,119 @@
+//! Examples scraped from the puzzle description, and the answers to them that the description
+//! mentions.
+
+use kuchiki::NodeRef;
+use std::fs;
+use std::io;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    /// Expected answers as `(part, answer)` pairs.
+    pub answers: Vec<(u32, String)>,
+}
+
+/// The file with the answers that `extract` found for an example, one `part answer` per line. An
+/// empty file means that it found none: either the description gives none for that example, or it
+/// gives them in a way that the heuristic misses. Tests can then give the answers themselves.
+pub fn answers_file_name(year: u32, day: u32, index: usize) -> String {
+    format!("examples/{}/{:02}-{}.answers", year, day, index)
+}
+
+/// Extracts all `<pre>` blocks from the page as examples, along with their answers.
+///
+/// Answers are highlighted in the text as `<code><em>...</em></code>`. The last such highlight
+/// after an example and before the next one is taken as the answer to that example, for the part
+/// whose description it appears in. Part two often refers back to an example from part one; in
+/// that case the answer is attributed to the most recent example before it.
+pub fn extract(root: &NodeRef) -> Vec<Example> {
+    let pres = root.select("pre").unwrap().map(|pre| pre.as_node().clone()).collect::<Vec<_>>();
+    let mut examples = pres
+        .iter()
+        .map(|pre| Example { input: pre.text_contents(), answers: Vec::new() })
+        .collect::<Vec<_>>();
+
+    let mut current = None;
+    for (part, article) in (1..=2).zip(root.select("article.day-desc").unwrap()) {
+        let mut answer = None;
+        for node in article.as_node().descendants() {
+            if let Some(index) = pres.iter().position(|pre| *pre == node) {
+                record_answer(&mut examples, current, part, answer.take());
+                current = Some(index);
+            } else if is_highlighted_answer(&node) {
+                answer = Some(node.text_contents());
+            }
+        }
+        record_answer(&mut examples, current, part, answer);
+    }
+    examples
+}
+
+fn record_answer(examples: &mut [Example], index: Option<usize>, part: u32, answer: Option<String>) {
+    if let (Some(index), Some(answer)) = (index, answer) {
+        let answers = &mut examples[index].answers;
+        answers.retain(|(p, _)| *p != part);
+        answers.push((part, answer));
+    }
+}
+
+/// Whether the node is a `<code>` element consisting of just an `<em>` element, outside a `<pre>`.
+fn is_highlighted_answer(node: &NodeRef) -> bool {
+    let is_element = |node: &NodeRef, name: &str| node.as_element().map_or(false, |element| &*element.name.local == name);
+    is_element(node, "code") &&
+        node.children().filter(|child| child.as_text().map_or(true, |text| !text.borrow().trim().is_empty())).count() == 1 &&
+        node.children().any(|child| is_element(&child, "em")) &&
+        !node.ancestors().any(|ancestor| is_element(&ancestor, "pre"))
+}
+
+pub fn write_answers(year: u32, day: u32, index: usize, example: &Example) -> io::Result<()> {
+    let contents = example.answers
+        .iter()
+        .map(|(part, answer)| format!("{} {}\n", part, answer))
+        .collect::<String>();
+    fs::write(answers_file_name(year, day, index), contents)
+}
+
+pub fn parse_answers(contents: &str) -> Vec<(u32, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let space = line.find(' ')?;
+            Some((line[..space].parse().ok()?, line[space + 1..].to_string()))
+        })
+        .collect()
+}
+
+#[test]
+fn test_extract() {
+    use kuchiki::parse_html;
+    use kuchiki::traits::TendrilSink;
+
+    let root = parse_html().one("\
+        <main>\
+        <article class=\"day-desc\"><h2>--- Day 1: Report Repair ---</h2>\
+        <p>Before any example: <code><em>ignored</em></code>.</p>\
+        <pre><code>1721\n979\n</code></pre>\
+        <p>The entries <code>1721</code> and <code>299</code> multiply to <code>514579</code>, \
+        so the correct answer is <code><em>514579</em></code>.</p>\
+        <pre><code>1\n2\n</code></pre>\
+        <p>Here, <code><em>1</em> + 2</code> is not an answer, nor is <em>this</em>.</p>\
+        </article>\
+        <p>Your puzzle answer was <code>1005459</code>.</p>\
+        <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+        <p>In the example above, the product is <code><em>241861950</em></code>.</p>\
+        <pre><code>3\n</code></pre>\
+        <p>This has <code><em>6</em></code> and then <code><em>7</em></code>.</p>\
+        </article>\
+        </main>");
+    assert_eq!(extract(&root), vec![
+        Example { input: "1721\n979\n".to_string(), answers: vec![(1, "514579".to_string())] },
+        Example { input: "1\n2\n".to_string(), answers: vec![(2, "241861950".to_string())] },
+        Example { input: "3\n".to_string(), answers: vec![(2, "7".to_string())] },
+    ]);
+}
+
+#[test]
+fn test_parse_answers() {
+    assert_eq!(parse_answers("1 514579\n2 abc def\n"), vec![(1, "514579".to_string()), (2, "abc def".to_string())]);
+    assert_eq!(parse_answers(""), vec![]);
+}
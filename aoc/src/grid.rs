@@ -0,0 +1,306 @@
+//! A rectangular grid of cells, as found in many puzzle inputs.
+//!
+//! Coordinates are signed, with `x` going right and `y` going down from the top left corner, so
+//! that neighbors and rays can step outside the grid and be checked against its bounds.
+
+use crate::parse;
+use crate::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// Offsets to the 4 orthogonal neighbors, clockwise from the top.
+pub const DIRECTIONS4: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to the 8 orthogonal and diagonal neighbors, clockwise from the top.
+pub const DIRECTIONS8: [(i64, i64); 8] = [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: i64,
+    height: i64,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order.
+    pub fn from_vec(width: i64, height: i64, cells: Vec<T>) -> Grid<T> {
+        assert!(width >= 0 && height >= 0);
+        assert_eq!(cells.len(), (width * height) as usize);
+        Grid { width, height, cells }
+    }
+
+    pub fn from_fn<F: FnMut(i64, i64) -> T>(width: i64, height: i64, mut f: F) -> Grid<T> {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| f(x, y)).collect();
+        Grid::from_vec(width, height, cells)
+    }
+
+    pub fn width(&self) -> i64 {
+        self.width
+    }
+
+    pub fn height(&self) -> i64 {
+        self.height
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        0 <= x && x < self.width && 0 <= y && y < self.height
+    }
+
+    fn offset(&self, x: i64, y: i64) -> Option<usize> {
+        if self.contains(x, y) { Some((y * self.width + x) as usize) } else { None }
+    }
+
+    /// Returns the cell at the given position, or `None` if it is outside the grid.
+    pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+        self.offset(x, y).map(move |i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+        self.offset(x, y).map(move |i| &mut self.cells[i])
+    }
+
+    /// Returns the cell at the given position, as if the grid repeated infinitely in every
+    /// direction. Panics if the grid is empty.
+    pub fn get_wrapping(&self, x: i64, y: i64) -> &T {
+        &self[(x.rem_euclid(self.width), y.rem_euclid(self.height))]
+    }
+
+    /// Iterates over the cells in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<T> {
+        self.cells.iter()
+    }
+
+    /// Iterates over the positions and cells in row-major order.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, cell)| ((i as i64 % width, i as i64 / width), cell))
+    }
+
+    pub fn rows(&self) -> std::slice::Chunks<T> {
+        self.cells.chunks(self.width.max(1) as usize)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::from_vec(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Iterates over the positions of the orthogonal neighbors that are inside the grid.
+    pub fn neighbors4(&self, x: i64, y: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.neighbors(x, y, &DIRECTIONS4)
+    }
+
+    /// Iterates over the positions of the orthogonal and diagonal neighbors that are inside the
+    /// grid.
+    pub fn neighbors8(&self, x: i64, y: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.neighbors(x, y, &DIRECTIONS8)
+    }
+
+    fn neighbors<'a>(&'a self, x: i64, y: i64, directions: &'static [(i64, i64)]) -> impl Iterator<Item = (i64, i64)> + 'a {
+        directions.iter().map(move |&(dx, dy)| (x + dx, y + dy)).filter(move |&(x, y)| self.contains(x, y))
+    }
+
+    /// Iterates over the positions from `(x, y)` in steps of `(dx, dy)`, excluding `(x, y)`
+    /// itself, until the edge of the grid. Combine with `find` to look for the first cell of
+    /// interest in a line of sight.
+    pub fn ray(&self, x: i64, y: i64, dx: i64, dy: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        assert!(dx != 0 || dy != 0);
+        (1..).map(move |i| (x + i * dx, y + i * dy)).take_while(move |&(x, y)| self.contains(x, y))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: i64, height: i64, value: T) -> Grid<T> {
+        assert!(width >= 0 && height >= 0);
+        Grid::from_vec(width, height, vec![value; (width * height) as usize])
+    }
+
+    /// Returns a copy of the rectangle with the given top left corner and size, which must lie
+    /// inside the grid.
+    pub fn sub_grid(&self, x: i64, y: i64, width: i64, height: i64) -> Grid<T> {
+        assert!(width >= 0 && height >= 0);
+        assert!(x >= 0 && y >= 0 && x + width <= self.width && y + height <= self.height);
+        Grid::from_fn(width, height, |dx, dy| self[(x + dx, y + dy)].clone())
+    }
+
+    /// Copies `other` into this grid with its top left corner at `(x, y)`. It must fit inside
+    /// this grid.
+    pub fn blit(&mut self, x: i64, y: i64, other: &Grid<T>) {
+        assert!(x >= 0 && y >= 0 && x + other.width <= self.width && y + other.height <= self.height);
+        for (dy, row) in other.rows().enumerate() {
+            let start = self.offset(x, y + dy as i64).unwrap();
+            self.cells[start..start + row.len()].clone_from_slice(row);
+        }
+    }
+
+    /// Returns the grid rotated and/or flipped. Rotating by a quarter turn swaps the width and
+    /// the height.
+    pub fn transformed(&self, t: Transformation) -> Grid<T> {
+        let (width, height) = t.size(self.width, self.height);
+        let mut cells = self.cells.clone();
+        for ((x, y), cell) in self.enumerate() {
+            let (x, y) = t.apply(x, y, self.width, self.height);
+            cells[(y * width + x) as usize] = cell.clone();
+        }
+        Grid::from_vec(width, height, cells)
+    }
+}
+
+impl<T> Index<(i64, i64)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (i64, i64)) -> &T {
+        match self.get(x, y) {
+            Some(cell) => cell,
+            None => panic!("({}, {}) is outside of a {}x{} grid", x, y, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<(i64, i64)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (i64, i64)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(x, y) {
+            Some(cell) => cell,
+            None => panic!("({}, {}) is outside of a {}x{} grid", x, y, width, height),
+        }
+    }
+}
+
+/// Parses a grid of characters as-is. Use `parse::grid` to convert them.
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Grid<char>, ParseError> {
+        parse::grid(s, Some)
+    }
+}
+
+/// Displays one row per line, with the cells next to each other.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows().take(self.height as usize) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// One of the 8 symmetries of a square: an optional horizontal flip, followed by a number of
+/// clockwise quarter turns.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Transformation(u8);
+
+impl Transformation {
+    pub const IDENTITY: Transformation = Transformation(0);
+
+    pub fn new(quarter_turns: u8, flipped: bool) -> Transformation {
+        Transformation((quarter_turns % 4) | if flipped { 0b100 } else { 0 })
+    }
+
+    pub fn all() -> impl Iterator<Item = Transformation> {
+        (0..8).map(Transformation)
+    }
+
+    /// The index of this transformation in `all()`.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn from_index(index: usize) -> Transformation {
+        assert!(index < 8);
+        Transformation(index as u8)
+    }
+
+    pub fn quarter_turns(self) -> u8 {
+        self.0 & 0b11
+    }
+
+    pub fn flipped(self) -> bool {
+        self.0 & 0b100 != 0
+    }
+
+    /// The size of a `width` by `height` grid after the transformation.
+    pub fn size(self, width: i64, height: i64) -> (i64, i64) {
+        if self.quarter_turns() % 2 == 0 { (width, height) } else { (height, width) }
+    }
+
+    /// Where the position `(x, y)` in a `width` by `height` grid ends up after the
+    /// transformation.
+    pub fn apply(self, mut x: i64, mut y: i64, mut width: i64, mut height: i64) -> (i64, i64) {
+        if self.flipped() {
+            x = width - 1 - x;
+        }
+        for _ in 0..self.quarter_turns() {
+            let (new_x, new_y) = (height - 1 - y, x);
+            x = new_x;
+            y = new_y;
+            std::mem::swap(&mut width, &mut height);
+        }
+        (x, y)
+    }
+}
+
+#[test]
+fn test_access() {
+    let mut grid = Grid::from_fn(3, 2, |x, y| x + 10 * y);
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!((grid.get(2, 1), grid.get(3, 0), grid.get(0, -1)), (Some(&12), None, None));
+    assert_eq!((*grid.get_wrapping(3, -1), *grid.get_wrapping(-7, 4)), (10, 2));
+    grid[(1, 1)] = 99;
+    *grid.get_mut(0, 0).unwrap() = -1;
+    assert_eq!(grid.iter().copied().collect::<Vec<_>>(), vec![-1, 1, 2, 10, 99, 12]);
+    assert_eq!(grid.enumerate().nth(4), Some(((1, 1), &99)));
+    assert_eq!(grid.map(|&n| n > 10).iter().filter(|&&b| b).count(), 2);
+}
+
+#[test]
+#[should_panic(expected = "(3, 0) is outside of a 3x2 grid")]
+fn test_index_out_of_bounds() {
+    let _ = Grid::new(3, 2, 0)[(3, 0)];
+}
+
+#[test]
+fn test_neighbors() {
+    let grid: Grid<char> = "#.#\n.L.\n..#\n".parse().unwrap();
+    assert_eq!(grid.neighbors4(0, 0).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+    assert_eq!(grid.neighbors8(1, 1).filter(|&p| grid[p] == '#').count(), 3);
+    assert_eq!(grid.neighbors8(2, 2).collect::<Vec<_>>(), vec![(2, 1), (1, 2), (1, 1)]);
+    assert_eq!(grid.ray(0, 0, 1, 1).collect::<Vec<_>>(), vec![(1, 1), (2, 2)]);
+    assert_eq!(grid.ray(0, 2, 1, -1).find(|&p| grid[p] != '.'), Some((1, 1)));
+    assert_eq!(grid.ray(1, 0, 0, 1).find(|&p| grid[p] == '#'), None);
+}
+
+#[test]
+fn test_transformed() {
+    let grid: Grid<char> = "ab\ncd\nef\n".parse().unwrap();
+    let transformed = Transformation::all().map(|t| grid.transformed(t).to_string()).collect::<Vec<_>>();
+    assert_eq!(transformed, vec![
+        "ab\ncd\nef\n",
+        "eca\nfdb\n",
+        "fe\ndc\nba\n",
+        "bdf\nace\n",
+        "ba\ndc\nfe\n",
+        "fdb\neca\n",
+        "ef\ncd\nab\n",
+        "ace\nbdf\n",
+    ]);
+    for t in Transformation::all() {
+        assert_eq!(Transformation::new(t.quarter_turns(), t.flipped()), t);
+        assert_eq!(Transformation::from_index(t.index()), t);
+    }
+    assert_eq!(grid.transformed(Transformation::new(1, false)).transformed(Transformation::new(3, false)), grid);
+}
+
+#[test]
+fn test_blit() {
+    let mut grid = Grid::new(4, 3, '.');
+    let source: Grid<char> = "abc\ndef\nghi\n".parse().unwrap();
+    grid.blit(1, 1, &source.sub_grid(1, 0, 2, 2));
+    grid.blit(0, 0, &Grid::new(1, 1, '#'));
+    assert_eq!(grid.to_string(), "#...\n.bc.\n.ef.\n");
+    assert_eq!(Grid::<char>::from_str("").unwrap().to_string(), "");
+}
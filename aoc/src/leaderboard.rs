@@ -0,0 +1,245 @@
+//! Client for private leaderboards, with a local standings report.
+
+use crate::unlock::{self, Clock};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// The website asks not to fetch a private leaderboard more often than this.
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+pub fn cache_file_name(year: u32, id: &str) -> String {
+    format!("leaderboards/{}/{}.json", year, id)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: String,
+    pub name: Option<String>,
+    pub local_score: u64,
+    /// Unix timestamps at which each star was obtained, keyed by day and part.
+    pub star_times: BTreeMap<(u32, u32), u64>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u32,
+    pub owner_id: String,
+    /// Sorted by descending local score, then by name.
+    pub members: Vec<Member>,
+}
+
+/// Older leaderboards encode numbers as strings, newer ones as numbers; accept both.
+fn as_u64(value: &Value) -> Option<u64> {
+    value.as_u64().or_else(|| value.as_str()?.parse().ok())
+}
+
+fn as_string(value: &Value) -> Option<String> {
+    value.as_str().map(str::to_string).or_else(|| value.as_u64().map(|n| n.to_string()))
+}
+
+impl Leaderboard {
+    pub fn from_json(json: &str) -> Result<Leaderboard, Box<dyn Error>> {
+        let value: Value = serde_json::from_str(json)?;
+        Leaderboard::from_value(&value).ok_or_else(|| "unexpected leaderboard format".into())
+    }
+
+    fn from_value(value: &Value) -> Option<Leaderboard> {
+        let mut members = value.get("members")?
+            .as_object()?
+            .values()
+            .map(|member| {
+                let mut star_times = BTreeMap::new();
+                for (day, parts) in member.get("completion_day_level")?.as_object()? {
+                    for (part, star) in parts.as_object()? {
+                        star_times.insert((day.parse().ok()?, part.parse().ok()?), as_u64(star.get("get_star_ts")?)?);
+                    }
+                }
+                Some(Member {
+                    id: as_string(member.get("id")?)?,
+                    name: member.get("name").and_then(Value::as_str).map(str::to_string),
+                    local_score: as_u64(member.get("local_score")?)?,
+                    star_times,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        members.sort_by(|a, b| b.local_score.cmp(&a.local_score).then_with(|| a.display_name().cmp(&b.display_name())));
+        Some(Leaderboard {
+            year: as_u64(value.get("event")?)? as u32,
+            owner_id: as_string(value.get("owner_id")?)?,
+            members,
+        })
+    }
+
+    /// Computes the local score of each member the way the website does: for every star, the
+    /// first member to get it scores as many points as there are members, the second one point
+    /// less, and so on.
+    pub fn compute_local_scores(&self) -> BTreeMap<String, u64> {
+        let num_members = self.members.len() as u64;
+        let mut scores = self.members.iter().map(|member| (member.id.clone(), 0)).collect::<BTreeMap<_, _>>();
+        let mut stars = BTreeMap::<(u32, u32), Vec<(u64, &str)>>::new();
+        for member in &self.members {
+            for (&star, &time) in &member.star_times {
+                stars.entry(star).or_default().push((time, &member.id));
+            }
+        }
+        for times in stars.values_mut() {
+            times.sort_unstable();
+            for (rank, (_, id)) in times.iter().enumerate() {
+                *scores.get_mut(*id).unwrap() += num_members - rank as u64;
+            }
+        }
+        scores
+    }
+
+    /// Formats the standings, with the time each member took for each star since the puzzle
+    /// unlocked, and the time between the two parts.
+    pub fn report(&self) -> String {
+        let mut out = String::new();
+        let scores = self.compute_local_scores();
+        for (rank, member) in self.members.iter().enumerate() {
+            writeln!(out, "{:3}) {:5} {:2}* {}", rank + 1, member.local_score, member.star_times.len(), member.display_name()).unwrap();
+            if scores[&member.id] != member.local_score {
+                writeln!(out, "       (computed local score: {})", scores[&member.id]).unwrap();
+            }
+            let days = member.star_times.keys().map(|&(day, _)| day).collect::<std::collections::BTreeSet<_>>();
+            for day in days {
+                let unlock = unlock::unlock_time(self.year, day).duration_since(UNIX_EPOCH).unwrap().as_secs();
+                let part1 = member.star_times.get(&(day, 1));
+                let part2 = member.star_times.get(&(day, 2));
+                write!(out, "       day {:2}:", day).unwrap();
+                if let Some(&part1) = part1 {
+                    write!(out, " part 1 {:>10}", format_duration(part1.saturating_sub(unlock))).unwrap();
+                }
+                if let Some(&part2) = part2 {
+                    write!(out, "  part 2 {:>10}", format_duration(part2.saturating_sub(unlock))).unwrap();
+                    if let Some(&part1) = part1 {
+                        write!(out, "  (+{})", format_duration(part2.saturating_sub(part1))).unwrap();
+                    }
+                }
+                writeln!(out).unwrap();
+            }
+        }
+        out
+    }
+}
+
+fn format_duration(secs: u64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+const FIXTURE: &str = include_str!("../fixtures/leaderboard-2020.json");
+
+#[test]
+fn test_from_json() {
+    let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+    assert_eq!(leaderboard.year, 2020);
+    assert_eq!(leaderboard.owner_id, "123456");
+    assert_eq!(
+        leaderboard.members.iter().map(|member| (member.display_name(), member.local_score, member.star_times.len())).collect::<Vec<_>>(),
+        vec![
+            ("Alice".to_string(), 21, 6),
+            ("Bob".to_string(), 18, 5),
+            ("(anonymous user #345678)".to_string(), 8, 4),
+            ("Carol Example".to_string(), 0, 0),
+        ]);
+    assert_eq!(leaderboard.members[0].star_times[&(1, 2)], 1606799455);
+
+    let newer = Leaderboard::from_json(r#"{"owner_id": 1, "event": "2022", "members": {"1": {
+        "id": 1, "name": null, "stars": 1, "local_score": 1, "global_score": 0, "last_star_ts": 1669870861,
+        "completion_day_level": {"1": {"1": {"get_star_ts": 1669870861, "star_index": 0}}}}}}"#).unwrap();
+    assert_eq!(newer.owner_id, "1");
+    assert_eq!(newer.members[0].star_times[&(1, 1)], 1669870861);
+
+    assert!(Leaderboard::from_json("{}").is_err());
+    assert!(Leaderboard::from_json("<html>").is_err());
+}
+
+#[test]
+fn test_compute_local_scores() {
+    let leaderboard = Leaderboard::from_json(FIXTURE).unwrap();
+    for member in &leaderboard.members {
+        assert_eq!(leaderboard.compute_local_scores()[&member.id], member.local_score, "{}", member.display_name());
+    }
+}
+
+#[test]
+fn test_report() {
+    let report = Leaderboard::from_json(FIXTURE).unwrap().report();
+    let lines = report.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "  1)    21  6* Alice");
+    assert_eq!(lines[1], "       day  1: part 1    0:05:12  part 2    0:10:55  (+0:05:43)");
+    assert!(lines.contains(&"       day  2: part 1    0:15:00"));
+    assert!(lines.contains(&"       day  3: part 1   24:01:40  part 2   24:03:20  (+0:01:40)"));
+    assert_eq!(*lines.last().unwrap(), "  4)     0  0* Carol Example");
+}
+
+/// Returns the leaderboard from the cache if it was fetched less than `MIN_REFRESH_INTERVAL` ago,
+/// and fetches and caches it otherwise. Also returns how long ago it was fetched.
+pub fn load<F>(clock: &dyn Clock, cache_file_name: &Path, fetch: F) -> Result<(Leaderboard, Duration), Box<dyn Error>>
+    where F: FnOnce() -> Result<String, Box<dyn Error>>
+{
+    let now = clock.now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    match fs::read_to_string(cache_file_name) {
+        Ok(contents) => {
+            let cached: Value = serde_json::from_str(&contents)?;
+            let fetched_at = cached.get("fetched_at").and_then(Value::as_u64).unwrap_or(0);
+            let age = Duration::from_secs(now.saturating_sub(fetched_at));
+            if age < MIN_REFRESH_INTERVAL {
+                let leaderboard = cached.get("leaderboard").and_then(Leaderboard::from_value)
+                    .ok_or("unexpected leaderboard format in cache")?;
+                return Ok((leaderboard, age));
+            }
+        },
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {},
+        Err(err) => return Err(err.into()),
+    }
+
+    let json = fetch()?;
+    let leaderboard = Leaderboard::from_json(&json)
+        .map_err(|err| format!("could not read leaderboard ({}); is the session cookie valid?", err))?;
+    let value: Value = serde_json::from_str(&json)?;
+    if let Some(dir) = cache_file_name.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(cache_file_name, serde_json::to_string_pretty(&json!({ "fetched_at": now, "leaderboard": value }))?)?;
+    Ok((leaderboard, Duration::from_secs(0)))
+}
+
+#[test]
+fn test_load() {
+    let dir = crate::testing::temp_dir("leaderboard-load");
+    let cache_file_name = dir.join("2020/123456.json");
+    let mut clock = crate::testing::FakeClock::new(unlock::unlock_time(2020, 4));
+    let num_fetches = std::cell::Cell::new(0);
+    let fetch = || {
+        num_fetches.set(num_fetches.get() + 1);
+        Ok(FIXTURE.to_string())
+    };
+
+    let (leaderboard, age) = load(&clock, &cache_file_name, fetch).unwrap();
+    assert_eq!((leaderboard.members.len(), age, num_fetches.get()), (4, Duration::from_secs(0), 1));
+
+    clock.sleep(Duration::from_secs(14 * 60));
+    let (cached, age) = load(&clock, &cache_file_name, fetch).unwrap();
+    assert_eq!((cached, age, num_fetches.get()), (leaderboard, Duration::from_secs(14 * 60), 1));
+
+    clock.sleep(Duration::from_secs(60));
+    let (_, age) = load(&clock, &cache_file_name, fetch).unwrap();
+    assert_eq!((age, num_fetches.get()), (Duration::from_secs(0), 2));
+
+    clock.sleep(Duration::from_secs(15 * 60));
+    assert!(load(&clock, &cache_file_name, || Ok("<html>Log in</html>".to_string())).is_err());
+}
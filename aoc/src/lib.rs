@@ -1,128 +1,634 @@
 use kuchiki::parse_html;
 use kuchiki::traits::TendrilSink;
+use std::collections::BTreeMap;
 use std::env;
 use std::error::Error;
-use std::fmt::Display;
+use std::fmt::{self, Display};
 use std::fs;
-use std::io::BufRead;
 use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
 use std::time::Instant;
+use crate::unlock::Clock;
 
-pub fn main<P1, P2, R1, R2>(part1: P1, part2: P2)
-    where P1: Fn(&str) -> R1, P2: Fn(&str) -> R2, R1: Display, R2: Display
-{
-    let year = year();
-    let day = day();
-    let input = input();
-    run(year, day, 1, part1, &input);
-    run(year, day, 2, part2, &input);
+pub mod allocations;
+mod answers;
+pub mod automaton;
+mod bench;
+mod cli;
+pub mod differential;
+mod error;
+mod examples;
+mod grid;
+mod leaderboard;
+pub mod math;
+pub mod parse;
+mod puzzle;
+pub mod random;
+mod scaffold;
+mod session;
+pub mod solution_tests;
+mod submit;
+mod summary;
+#[cfg(test)]
+mod testing;
+mod unlock;
+mod watch;
+
+pub use error::ParseError;
+pub use grid::{Grid, Transformation, DIRECTIONS4, DIRECTIONS8};
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// A solution to one day's puzzle. The input is parsed once by `parse`, and the result is handed
+/// to each of the parts.
+pub trait Solution {
+    const YEAR: u32;
+    const DAY: u32;
+
+    type Input;
+    type Output1: Answer;
+    type Output2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Output1;
+    fn part2(input: &Self::Input) -> Self::Output2;
+
+    fn input() -> String {
+        input(Self::YEAR, Self::DAY)
+    }
+
+    fn example(index: usize) -> String {
+        example(Self::YEAR, Self::DAY, index)
+    }
+
+    fn example_answer(index: usize, part: u32) -> Option<String> {
+        example_answer(Self::YEAR, Self::DAY, index, part)
+    }
+
+    /// Generates a valid input of the given size, whose meaning depends on the puzzle, to see how
+    /// the solution scales. Returns `None` if the day has no generator.
+    fn generate(_rng: &mut random::Rng, _size: usize) -> Option<String> {
+        None
+    }
 }
 
-pub fn input() -> String {
-    let year = year();
-    let day = day();
+/// The result of a part: either a plain answer, or a `Result` whose error is reported instead of
+/// the answer.
+pub trait Answer {
+    fn into_answer(self) -> Result<String, Box<dyn Error>>;
+}
+
+macro_rules! impl_answer {
+    ($($ty:ty),*) => {
+        $(
+            impl Answer for $ty {
+                fn into_answer(self) -> Result<String, Box<dyn Error>> {
+                    Ok(self.to_string())
+                }
+            }
+        )*
+    }
+}
+
+impl_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String, &str);
+
+impl<T: Display, E: Into<Box<dyn Error>>> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String, Box<dyn Error>> {
+        self.map(|answer| answer.to_string()).map_err(Into::into)
+    }
+}
+
+/// Maps day numbers to the solutions for those days, all of which must be for the same year.
+pub struct Registry {
+    year: u32,
+    days: BTreeMap<u32, ParseFn>,
+    generators: BTreeMap<u32, GenerateFn>,
+}
+
+/// Parses the input for one day, ready to run either part on.
+type ParseFn = fn(&str) -> Result<Box<dyn Parsed>, Box<dyn Error>>;
+
+/// Generates an input of the given size for one day, if it has a generator.
+type GenerateFn = fn(&mut random::Rng, usize) -> Option<String>;
+
+/// A day's parsed input, with the solution that parsed it.
+trait Parsed {
+    /// Runs the given part, returning the formatted answer.
+    fn run(&self, part: u32) -> Result<String, Box<dyn Error>>;
+}
+
+struct ParsedInput<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn run(&self, part: u32) -> Result<String, Box<dyn Error>> {
+        match part {
+            1 => S::part1(&self.0).into_answer(),
+            2 => S::part2(&self.0).into_answer(),
+            _ => panic!("invalid part {}", part),
+        }
+    }
+}
+
+impl Registry {
+    pub fn new(year: u32) -> Registry {
+        Registry { year, days: BTreeMap::new(), generators: BTreeMap::new() }
+    }
+
+    pub fn register<S: Solution + 'static>(&mut self) {
+        assert_eq!(S::YEAR, self.year, "day {} is for a different year", S::DAY);
+        let prev = self.days.insert(S::DAY, parse_input::<S>);
+        assert!(prev.is_none(), "day {} registered twice", S::DAY);
+        self.generators.insert(S::DAY, S::generate);
+    }
+
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = u32> + '_ {
+        self.days.keys().copied()
+    }
+
+    fn get(&self, day: u32) -> Option<ParseFn> {
+        self.days.get(&day).copied()
+    }
+
+    fn generator(&self, day: u32) -> Option<GenerateFn> {
+        self.generators.get(&day).copied()
+    }
+}
+
+fn parse_input<S: Solution + 'static>(input: &str) -> Result<Box<dyn Parsed>, Box<dyn Error>> {
+    let input = S::parse(input).map_err(|err| format!("invalid input at {}", err))?;
+    Ok(Box::new(ParsedInput::<S>(input)))
+}
+
+/// Parses the input and runs the given part on it, returning the formatted answer.
+fn solve(parse: ParseFn, input: &str, part: u32) -> Result<String, Box<dyn Error>> {
+    parse(input)?.run(part)
+}
+
+/// A phase of running a solution, which is timed separately from the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(u32),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// Entry point for the runner binary. See `cli::USAGE` for the arguments it accepts.
+pub fn main(registry: &Registry) {
+    cli::main(registry);
+}
+
+pub fn input(year: u32, day: u32) -> String {
+    try_input(year, day)
+        .unwrap_or_else(|err| panic!("could not get input for {} day {}: {}", year, day, err))
+}
+
+fn try_input(year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+    migrate_flat_layout_once(year)?;
     let input_file_name = input_file_name(year, day);
     fs::read_to_string(&input_file_name)
-        .or_else(|_err| -> Result<String, Box<dyn Error>> {
+        .or_else(|_err| {
             println!("Input file {} could not be read, fetching...", input_file_name);
-            let contents = fetch_input(year, day)?;
-            fs::write(&input_file_name, &contents)?;
-            Ok(contents)
+            fetch_and_write_input(year, day)
         })
-        .unwrap()
-}
-
-pub fn example(index: usize) -> String {
-    let year = year();
-    let day = day();
-    let example_file_name = example_file_name(year, day, index);
-    fs::read_to_string(&example_file_name)
-        .or_else(|_err| -> Result<String, Box<dyn Error>> {
-            println!("Example file {} could not be read, fetching...", example_file_name);
-            let num_examples = fetch_examples(year, day)?;
-            if index >= num_examples {
-                panic!(
-                    "tried to read example {} but there are only {} examples for year {}, day {}",
-                    index, num_examples, year, day);
+}
+
+fn fetch_and_write_input(year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+    let contents = fetch_input(&base_url(), &load_session_cookie()?, year, day)?;
+    write_input(year, day, &contents)?;
+    Ok(contents)
+}
+
+fn write_input(year: u32, day: u32, contents: &str) -> io::Result<()> {
+    let input_file_name = input_file_name(year, day);
+    fs::create_dir_all(Path::new(&input_file_name).parent().unwrap())?;
+    fs::write(&input_file_name, contents)
+}
+
+/// Deletes the cached input, puzzle description, examples and example answers for the given day,
+/// so they will be fetched again when next needed. Returns the names of the deleted files.
+fn invalidate(year: u32, day: u32) -> io::Result<Vec<String>> {
+    let mut file_names = vec![input_file_name(year, day), puzzle::file_name(year, day)];
+    let examples_dir = format!("examples/{}", year);
+    if Path::new(&examples_dir).is_dir() {
+        let prefix = format!("{:02}-", day);
+        for entry in fs::read_dir(&examples_dir)? {
+            let file_name = entry?.file_name().to_string_lossy().into_owned();
+            if file_name.starts_with(&prefix) && (file_name.ends_with(".example") || file_name.ends_with(".answers")) {
+                file_names.push(format!("{}/{}", examples_dir, file_name));
+            }
+        }
+    }
+    file_names.sort();
+    let mut deleted = Vec::new();
+    for file_name in file_names {
+        match fs::remove_file(&file_name) {
+            Ok(()) => deleted.push(file_name),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {},
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(deleted)
+}
+
+pub fn example(year: u32, day: u32, index: usize) -> String {
+    try_example(year, day, index)
+        .unwrap_or_else(|err| panic!("could not get example {} for {} day {}: {}", index, year, day, err))
+}
+
+fn try_example(year: u32, day: u32, index: usize) -> Result<String, Box<dyn Error>> {
+    migrate_flat_layout_once(year)?;
+    let file_name = example_file_name(year, day, index);
+    fs::read_to_string(&file_name)
+        .or_else(|_err| {
+            println!("Example file {} could not be read, fetching...", file_name);
+            let examples = fetch_and_write_puzzle(year, day)?.examples;
+            match examples.get(index) {
+                Some(example) => Ok(example.input.clone()),
+                None => Err(format!("there are only {} examples", examples.len()).into()),
             }
-            let contents = fs::read_to_string(&example_file_name)
-                .unwrap();
-            Ok(contents)
         })
-        .unwrap()
 }
 
-fn year() -> u32 {
-    2020
+/// Returns the answer to the given part for the given example, as mentioned in the puzzle
+/// description, or `None` if no answer could be found, or the puzzle could not be fetched.
+pub fn example_answer(year: u32, day: u32, index: usize, part: u32) -> Option<String> {
+    migrate_flat_layout_once(year).ok()?;
+    let file_name = examples::answers_file_name(year, day, index);
+    let answers = fs::read_to_string(&file_name)
+        .map(|contents| examples::parse_answers(&contents))
+        .or_else(|_err| -> Result<_, Box<dyn Error>> {
+            println!("Example answers file {} could not be read, fetching...", file_name);
+            let examples = fetch_and_write_puzzle(year, day)?.examples;
+            Ok(examples.get(index).map_or_else(Vec::new, |example| example.answers.clone()))
+        })
+        .ok()?;
+    answers.into_iter().find(|(p, _)| *p == part).map(|(_, answer)| answer)
+}
+
+fn fetch_and_write_puzzle(year: u32, day: u32) -> Result<puzzle::Puzzle, Box<dyn Error>> {
+    let puzzle = fetch_puzzle(&base_url(), &load_session_cookie()?, year, day)?;
+    write_puzzle(year, day, &puzzle)?;
+    Ok(puzzle)
+}
+
+fn write_puzzle(year: u32, day: u32, puzzle: &puzzle::Puzzle) -> io::Result<()> {
+    puzzle.write_description(year, day)?;
+    fs::create_dir_all(Path::new(&example_file_name(year, day, 0)).parent().unwrap())?;
+    for (i, example) in puzzle.examples.iter().enumerate() {
+        fs::write(&example_file_name(year, day, i), &example.input)?;
+        examples::write_answers(year, day, i, example)?;
+    }
+    Ok(())
 }
 
-fn day() -> u32 {
-    env::current_exe().unwrap()
-        .file_stem().unwrap()
-        .to_str().unwrap()
-        .get(0..2).unwrap()
-        .parse::<u32>().unwrap()
+/// Returns the puzzle description as Markdown. It is fetched if it is not cached yet, or if only
+/// part one is cached but part two has been unlocked since, as shown by a recorded answer to part
+/// one.
+fn puzzle_description(year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+    match fs::read_to_string(puzzle::file_name(year, day)) {
+        Ok(description) if puzzle::has_part_two(&description) || answers::load(year, day, 1).is_none() =>
+            Ok(description),
+        _ => Ok(fetch_and_write_puzzle(year, day)?.description),
+    }
 }
 
-fn run<P, R>(year: u32, day: u32, part: u32, func: P, input: &str)
-    where P: Fn(&str) -> R, R: Display
+/// Waits until the puzzle unlocks, then fetches its input, description and examples, retrying
+/// with backoff while the website is not serving them yet.
+fn wait_and_fetch(clock: &mut dyn Clock, base_url: &str, session_cookie: &str, year: u32, day: u32)
+    -> Result<(String, puzzle::Puzzle), Box<dyn Error>>
+{
+    unlock::wait_until(clock, unlock::unlock_time(year, day));
+    let input = unlock::retry_with_backoff(clock, || fetch_input(base_url, session_cookie, year, day))?;
+    let puzzle = unlock::retry_with_backoff(clock, || fetch_puzzle(base_url, session_cookie, year, day))?;
+    Ok((input, puzzle))
+}
+
+#[test]
+fn test_wait_and_fetch() {
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    let num_requests = AtomicUsize::new(0);
+    let server = testing::MockServer::start(move |request| match (request.path.as_str(), num_requests.fetch_add(1, Ordering::SeqCst)) {
+        ("/2020/day/1/input", 0) => (404, testing::MOCK_NOT_UNLOCKED.to_string()),
+        ("/2020/day/1/input", 1) => (500, "Internal Server Error".to_string()),
+        ("/2020/day/1/input", _) => (200, testing::MOCK_INPUT.to_string()),
+        ("/2020/day/1", _) => (200, testing::MOCK_PUZZLE_HTML.to_string()),
+        _ => (404, "404 Not Found".to_string()),
+    });
+    let unlock = unlock::unlock_time(2020, 1);
+    let mut clock = testing::FakeClock::new(unlock - Duration::from_secs(5));
+    let (input, puzzle) = wait_and_fetch(&mut clock, &server.base_url, "cookie", 2020, 1).unwrap();
+    assert_eq!(input, testing::MOCK_INPUT);
+    assert_eq!(puzzle.examples.len(), 2);
+    assert_eq!(clock.now.get(), unlock + Duration::from_secs(1 + 2));
+    assert_eq!(server.requests().len(), 4);
+
+    // An invalid session cookie won't become valid by waiting.
+    let server = testing::mock_aoc_server();
+    let mut clock = testing::FakeClock::new(unlock);
+    let err = wait_and_fetch(&mut clock, &server.base_url, "cookie", 2020, 1).unwrap_err();
+    assert!(err.to_string().contains("is the session cookie valid?"), "{}", err);
+    assert!(clock.sleeps.is_empty());
+    assert_eq!(server.requests().len(), 1);
+}
+
+/// Runs `func`, and returns how long it took and what it allocated, if allocations are counted.
+fn measure<F, R>(func: F) -> (R, String)
+    where F: FnOnce() -> R
 {
     let start = Instant::now();
-    let output = func(input);
+    let (result, usage) = allocations::measure(func);
     let duration = start.elapsed();
+    let mut cost = format!("{}.{:03} s", duration.as_secs(), duration.subsec_millis());
+    if let Some(usage) = usage {
+        cost += &format!(", {}", usage);
+    }
+    (result, cost)
+}
 
-    println!(
-        "Answer to {} day {}, part {} ({}.{:03} s): {}",
-        year, day, part, duration.as_secs(), duration.subsec_millis(), output);
+/// Parses the input for one day, and prints how long that took. Returns `None` if parsing failed.
+fn run_parse(year: u32, day: u32, parse: ParseFn, input: &str) -> Option<Box<dyn Parsed>> {
+    let (parsed, cost) = measure(|| parse(input));
+    match parsed {
+        Ok(parsed) => {
+            println!("Parsed input for {} day {} ({})", year, day, cost);
+            Some(parsed)
+        },
+        Err(err) => {
+            eprintln!("Error in {} day {}, {}: {}", year, day, Phase::Parse, err);
+            None
+        },
+    }
 }
 
-fn input_file_name(_year: u32, day: u32) -> String {
-    format!("inputs/{:02}.in", day)
+/// Runs one part, and prints its answer, verified against the recorded answer if `verify` is set.
+/// Returns `None` if the part failed.
+fn run<P>(year: u32, day: u32, part: u32, verify: bool, func: P) -> Option<answers::Verdict>
+    where P: FnOnce() -> Result<String, Box<dyn Error>>
+{
+    let (output, cost) = measure(func);
+    let output = match output {
+        Ok(output) => output,
+        Err(err) => {
+            eprintln!("Error in {} day {}, {}: {}", year, day, Phase::Part(part), err);
+            return None;
+        },
+    };
+    print!("Answer to {} day {}, part {} ({}): {}", year, day, part, cost, output);
+    if !verify {
+        println!();
+        return Some(answers::Verdict::Unknown);
+    }
+    let verdict = answers::verify(year, day, part, &output);
+    println!(" [{}]", verdict);
+    Some(verdict)
 }
 
-fn example_file_name(_year: u32, day: u32, index: usize) -> String {
-    format!("examples/{:02}-{}.example", day, index)
+fn input_file_name(year: u32, day: u32) -> String {
+    format!("inputs/{}/{:02}.in", year, day)
 }
 
-fn load_session_cookie() -> Result<String, io::Error> {
-    let cookie_file_name = ".session_cookie";
-    fs::read_to_string(cookie_file_name)
-        .map(|s| s.trim().to_string())
-        .or_else(|_err| -> Result<String, io::Error> {
-            println!("No session cookie found. Please log in to https://adventofcode.com/ in your browser, open the browser console, copy the value of the 'session' cookie, and paste it here:");
-            let mut line = String::new();
-            io::stdin().lock().read_line(&mut line)?;
-            fs::write(&cookie_file_name, &line)?;
-            Ok(line.trim().to_string())
-        })
+fn example_file_name(year: u32, day: u32, index: usize) -> String {
+    format!("examples/{}/{:02}-{}.example", year, day, index)
 }
 
-fn fetch_input(year: u32, day: u32) -> Result<String, Box<dyn Error>> {
-    let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
-    send_get_request(&url)?
-        .text()
-        .map_err(From::from)
+/// Moves input and example files from the old layout without a year directory (`inputs/DD.in`,
+/// `examples/DD-N.example`) into the directory for the given year.
+fn migrate_flat_layout(root: &Path, year: u32) -> io::Result<()> {
+    migrate_flat_dir(&root.join("inputs"), year, is_flat_input_file_name)?;
+    migrate_flat_dir(&root.join("examples"), year, is_flat_example_file_name)
 }
 
-fn fetch_examples(year: u32, day: u32) -> Result<usize, Box<dyn Error>> {
-    let url = format!("https://adventofcode.com/{}/day/{}", year, day);
-    let mut response = send_get_request(&url)?;
-    let root = parse_html().from_utf8().read_from(&mut response)?;
-    let mut num_examples = 0;
-    for (index, node) in root.select("pre").unwrap().enumerate() {
-        fs::write(&example_file_name(year, day, index), node.text_contents())?;
-        num_examples += 1;
+fn migrate_flat_dir(dir: &Path, year: u32, is_flat_file_name: fn(&str) -> bool) -> io::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let year_dir = dir.join(year.to_string());
+    for entry in fs::read_dir(dir)? {
+        let file_name = entry?.file_name();
+        if !file_name.to_str().map_or(false, is_flat_file_name) {
+            continue;
+        }
+        fs::create_dir_all(&year_dir)?;
+        let src = dir.join(&file_name);
+        let dest = year_dir.join(&file_name);
+        if dest.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("cannot migrate {} because {} already exists", src.display(), dest.display())));
+        }
+        println!("Moving {} to {}", src.display(), dest.display());
+        fs::rename(src, dest)?;
     }
-    Ok(num_examples)
+    Ok(())
 }
 
-fn send_get_request(url: &str) -> Result<reqwest::Response, Box<dyn Error>> {
+/// Runs `migrate_flat_layout` on the current directory, only the first time it is called, because
+/// tests that run in parallel would race to move the same files. If that failed, later calls fail
+/// too.
+fn migrate_flat_layout_once(year: u32) -> Result<(), Box<dyn Error>> {
+    static MIGRATION: Once = Once::new();
+    static FAILED: AtomicBool = AtomicBool::new(false);
+    let mut result = Ok(());
+    MIGRATION.call_once(|| {
+        result = migrate_flat_layout(Path::new("."), year);
+        FAILED.store(result.is_err(), Ordering::SeqCst);
+    });
+    result?;
+    if FAILED.load(Ordering::SeqCst) {
+        return Err("moving the inputs and examples into per-year directories failed earlier".into());
+    }
+    Ok(())
+}
+
+fn is_flat_input_file_name(file_name: &str) -> bool {
+    file_name.strip_suffix(".in").map_or(false, |day| is_number(day, 2))
+}
+
+fn is_flat_example_file_name(file_name: &str) -> bool {
+    file_name.strip_suffix(".example").map_or(false, |stem| match stem.find('-') {
+        Some(dash) => is_number(&stem[..dash], 2) && is_number(&stem[dash + 1..], stem.len() - dash - 1),
+        None => false,
+    })
+}
+
+fn is_number(s: &str, len: usize) -> bool {
+    len > 0 && s.len() == len && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[test]
+fn test_migrate_flat_layout() {
+    let root = testing::temp_dir("migrate-flat-layout");
+    fs::create_dir_all(root.join("inputs")).unwrap();
+    fs::create_dir_all(root.join("examples/2019")).unwrap();
+    for file_name in &["inputs/01.in", "inputs/.gitkeep", "inputs/notes.txt", "examples/07-0.example", "examples/07-12.example", "examples/2019/01-0.example"] {
+        fs::write(root.join(file_name), file_name).unwrap();
+    }
+
+    migrate_flat_layout(&root, 2020).unwrap();
+    assert_eq!(fs::read_to_string(root.join("inputs/2020/01.in")).unwrap(), "inputs/01.in");
+    assert_eq!(fs::read_to_string(root.join("examples/2020/07-0.example")).unwrap(), "examples/07-0.example");
+    assert_eq!(fs::read_to_string(root.join("examples/2020/07-12.example")).unwrap(), "examples/07-12.example");
+    for file_name in &["inputs/.gitkeep", "inputs/notes.txt", "examples/2019/01-0.example"] {
+        assert!(root.join(file_name).exists(), "{} was moved", file_name);
+    }
+    assert!(!root.join("inputs/01.in").exists());
+
+    migrate_flat_layout(&root, 2020).unwrap();
+    fs::write(root.join("inputs/01.in"), "again").unwrap();
+    assert!(migrate_flat_layout(&root, 2020).is_err());
+}
+
+/// Loads the session cookie and, the first time, checks that the website still accepts it.
+fn load_session_cookie() -> Result<String, Box<dyn Error>> {
+    static CHECKED: AtomicBool = AtomicBool::new(false);
+    let session = session::load()?;
+    if !CHECKED.load(Ordering::Relaxed) {
+        session::check(&base_url(), &session.cookie)
+            .map_err(|err| format!("{} (profile {}, from {})", err, session.profile, session.source))?;
+        CHECKED.store(true, Ordering::Relaxed);
+    }
+    Ok(session.cookie)
+}
+
+/// The website to fetch from and submit to. Can be overridden with the `AOC_BASE_URL` environment
+/// variable, for example to point at a local mirror.
+fn base_url() -> String {
+    env::var("AOC_BASE_URL").unwrap_or_else(|_| BASE_URL.to_string())
+}
+
+fn fetch_input(base_url: &str, session_cookie: &str, year: u32, day: u32) -> Result<String, Box<dyn Error>> {
+    let url = format!("{}/{}/day/{}/input", base_url, year, day);
+    let contents = send_get_request(&url, session_cookie)?.text()?;
+    check_input(&contents).map_err(|err| -> Box<dyn Error> {
+        let message = format!("{} returned a bogus input: {}", url, err);
+        if err == NOT_UNLOCKED { Box::new(unlock::TransientError(message)) } else { message.into() }
+    })?;
+    Ok(contents)
+}
+
+const NOT_UNLOCKED: &str = "the puzzle has not been unlocked yet";
+
+/// Checks that a downloaded input looks like an actual puzzle input, rather than one of the
+/// messages that the website sometimes returns instead.
+fn check_input(contents: &str) -> Result<(), &'static str> {
+    if contents.trim().is_empty() {
+        Err("it is empty")
+    } else if contents.contains("Please don't repeatedly request this endpoint before it unlocks") {
+        Err(NOT_UNLOCKED)
+    } else if contents.contains("Please log in") || contents.contains("[Log In]") {
+        Err("the website asked to log in; is the session cookie valid?")
+    } else if contents.trim_start().starts_with('<') && contents.to_ascii_lowercase().contains("<html") {
+        Err("it is an HTML page")
+    } else {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_check_input() {
+    assert_eq!(check_input(testing::MOCK_INPUT), Ok(()));
+    assert_eq!(check_input("<x=1, y=2>\n"), Ok(()));
+    assert!(check_input("").is_err());
+    assert!(check_input("\n \n").is_err());
+    assert!(check_input("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n").is_err());
+    assert!(check_input("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n").is_err());
+    assert!(check_input("<!DOCTYPE html>\n<html lang=\"en-us\"><body><a href=\"/2020/auth/login\">[Log In]</a></body></html>").is_err());
+    assert!(check_input("<!DOCTYPE html>\n<HTML><body>Oops</body></HTML>").is_err());
+}
+
+#[test]
+fn test_fetch_input() {
+    let server = testing::mock_aoc_server();
+    assert_eq!(fetch_input(&server.base_url, testing::MOCK_SESSION_COOKIE, 2020, 1).unwrap(), testing::MOCK_INPUT);
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2020/day/1/input");
+    assert_eq!(requests[0].header("Cookie"), Some("session=0123456789abcdef"));
+}
+
+#[test]
+fn test_fetch_input_errors() {
+    let server = testing::mock_aoc_server();
+    let error = fetch_input(&server.base_url, "expired", 2020, 1).unwrap_err().to_string();
+    assert!(error.contains("400") && error.contains("session cookie"), "{}", error);
+    let error = fetch_input(&server.base_url, testing::MOCK_SESSION_COOKIE, 2020, 2).unwrap_err().to_string();
+    assert!(error.contains("404") && error.contains("unlocked"), "{}", error);
+    let error = fetch_input(&server.base_url, testing::MOCK_SESSION_COOKIE, 2020, 13).unwrap_err().to_string();
+    assert!(error.contains("500"), "{}", error);
+    let error = fetch_input(&server.base_url, testing::MOCK_SESSION_COOKIE, 2020, 3).unwrap_err().to_string();
+    assert!(error.contains("bogus input") && error.contains("unlocked"), "{}", error);
+}
+
+/// Fetches the puzzle page and returns its description as Markdown, along with the contents of
+/// all its `<pre>` blocks, which are usually the examples, and their answers.
+fn fetch_puzzle(base_url: &str, session_cookie: &str, year: u32, day: u32) -> Result<puzzle::Puzzle, Box<dyn Error>> {
+    let url = format!("{}/{}/day/{}", base_url, year, day);
+    let mut response = send_get_request(&url, session_cookie)?;
+    let root = parse_html().from_utf8().read_from(&mut response)?;
+    puzzle::Puzzle::from_html(&root).ok_or_else(|| format!("{} does not contain a puzzle description", url).into())
+}
+
+#[test]
+fn test_fetch_puzzle() {
+    let server = testing::mock_aoc_server();
+    let puzzle = fetch_puzzle(&server.base_url, testing::MOCK_SESSION_COOKIE, 2020, 1).unwrap();
+    assert!(puzzle.description.starts_with("## --- Day 1: Report Repair ---\n\nFor example,"), "{}", puzzle.description);
+    assert!(puzzle.description.contains("\n```\na < b && c\n```\n"), "{}", puzzle.description);
+    assert!(!puzzle::has_part_two(&puzzle.description));
+    let examples = puzzle.examples;
+    assert_eq!(
+        examples.iter().map(|example| example.input.as_str()).collect::<Vec<_>>(),
+        vec!["1721\n979\n366\n", "a < b && c\n"]);
+    assert_eq!(examples[0].answers, vec![(1, "514579".to_string())]);
+    assert_eq!(server.requests()[0].path, "/2020/day/1");
+    assert!(fetch_puzzle(&server.base_url, testing::MOCK_SESSION_COOKIE, 2020, 2).is_err());
+    assert!(fetch_puzzle(&server.base_url, testing::MOCK_SESSION_COOKIE, 2020, 13).is_err());
+    assert!(fetch_puzzle(&server.base_url, testing::MOCK_SESSION_COOKIE, 2020, 3).is_err());
+}
+
+fn fetch_leaderboard(base_url: &str, session_cookie: &str, year: u32, id: &str) -> Result<String, Box<dyn Error>> {
+    let url = format!("{}/{}/leaderboard/private/view/{}.json", base_url, year, id);
+    Ok(send_get_request(&url, session_cookie)?.text()?)
+}
+
+#[test]
+fn test_fetch_leaderboard() {
+    let server = testing::MockServer::start(|request| match request.path.as_str() {
+        "/2020/leaderboard/private/view/123456.json" => (200, include_str!("../fixtures/leaderboard-2020.json").to_string()),
+        _ => (404, "404 Not Found".to_string()),
+    });
+    let json = fetch_leaderboard(&server.base_url, "cookie", 2020, "123456").unwrap();
+    assert_eq!(leaderboard::Leaderboard::from_json(&json).unwrap().owner_id, "123456");
+    assert!(fetch_leaderboard(&server.base_url, "cookie", 2020, "654321").is_err());
+}
+
+fn send_get_request(url: &str, session_cookie: &str) -> Result<reqwest::Response, Box<dyn Error>> {
     let client = reqwest::Client::new();
-    let session_cookie = load_session_cookie()?;
     let response = client.get(url)
         .header(reqwest::header::COOKIE, format!("session={}", session_cookie))
-        .send()
-        .expect("request failed")
-        .error_for_status()?;
-    Ok(response)
+        .send()?;
+    let status = response.status();
+    match status.as_u16() {
+        400 => Err(format!("{} returned {}; is the session cookie valid?", url, status).into()),
+        404 => Err(Box::new(unlock::TransientError(format!("{} returned {}; has the puzzle been unlocked yet?", url, status)))),
+        _ if status.is_server_error() => Err(Box::new(unlock::TransientError(format!("{} returned {}", url, status)))),
+        _ if !status.is_success() => Err(format!("{} returned {}", url, status).into()),
+        _ => Ok(response),
+    }
 }
@@ -0,0 +1,219 @@
+//! Number theory: modular arithmetic, the Chinese remainder theorem and discrete logarithms.
+//!
+//! The functions accept any primitive integer type that converts losslessly to `i128`, and
+//! compute in 128 bits internally so that intermediate products don't overflow. Results are
+//! converted back to the argument type, and panic if they don't fit.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+/// A primitive integer type that the functions in this module work with.
+pub trait Integer: Copy + Into<i128> + TryFrom<i128> {}
+
+impl<T: Copy + Into<i128> + TryFrom<i128>> Integer for T {}
+
+fn wide<T: Integer>(n: T) -> i128 {
+    n.into()
+}
+
+fn narrow<T: Integer>(n: i128) -> T {
+    match T::try_from(n) {
+        Ok(n) => n,
+        Err(_) => panic!("{} does not fit in {}", n, std::any::type_name::<T>()),
+    }
+}
+
+fn positive_modulus(modulus: i128) -> u128 {
+    assert!(modulus > 0, "modulus must be positive, got {}", modulus);
+    modulus as u128
+}
+
+/// `(a + b) % m` for `a, b < m`, without overflow.
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b { a - (m - b) } else { a + b }
+}
+
+/// `(a * b) % m` for `a, b < m`, without overflow.
+fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let (mut a, mut b, mut product) = (a, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            product = add_mod(product, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    product
+}
+
+fn pow_mod(mut base: u128, mut exponent: u128, m: u128) -> u128 {
+    let mut result = 1 % m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Extended Euclidean algorithm. Returns a triple (r, s, t) such that:
+///
+/// ```text
+/// gcd(a, b) = r = a*s + b*t
+/// ```
+pub fn extended_gcd<T: Integer>(a: T, b: T) -> (T, T, T) {
+    let (gcd, s, t) = extended_gcd_wide(wide(a), wide(b));
+    (narrow(gcd), narrow(s), narrow(t))
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_rem, mut rem) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+    while rem != 0 {
+        let quotient = old_rem.div_euclid(rem);
+        let new_rem = old_rem - quotient * rem; old_rem = rem; rem = new_rem;
+        let new_s = old_s - quotient * s; old_s = s; s = new_s;
+        let new_t = old_t - quotient * t; old_t = t; t = new_t;
+    }
+    (old_rem, old_s, old_t)
+}
+
+#[test]
+fn test_extended_gcd() {
+    assert_eq!(extended_gcd(1, 1), (1, 0, 1));
+    assert_eq!(extended_gcd(3, 4), (1, -1, 1));
+    assert_eq!(extended_gcd(4, 3), (1, 1, -1));
+    assert_eq!(extended_gcd(12, 3), (3, 0, 1));
+    assert_eq!(extended_gcd(3, 12), (3, 1, 0));
+    assert_eq!(extended_gcd(12, 6), (6, 0, 1));
+    assert_eq!(extended_gcd(6, 12), (6, 1, 0));
+    assert_eq!(extended_gcd(12, 9), (3, 1, -1));
+    assert_eq!(extended_gcd(9i8, 12i8), (3, -1, 1));
+}
+
+/// `base^exponent mod modulus`, in `[0, modulus)`. The exponent must not be negative.
+pub fn mod_pow<T: Integer>(base: T, exponent: T, modulus: T) -> T {
+    let m = positive_modulus(wide(modulus));
+    let exponent = wide(exponent);
+    assert!(exponent >= 0, "exponent must not be negative, got {}", exponent);
+    let base = wide(base).rem_euclid(m as i128) as u128;
+    narrow(pow_mod(base, exponent as u128, m) as i128)
+}
+
+/// The `x` in `[0, modulus)` such that `a*x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime.
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+    let m = positive_modulus(wide(modulus)) as i128;
+    match extended_gcd_wide(wide(a).rem_euclid(m), m) {
+        (1, s, _) => Some(narrow(s.rem_euclid(m))),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_mod_pow_and_inverse() {
+    assert_eq!(mod_pow(7u64, 8, 20201227), 5764801);
+    assert_eq!(mod_pow(-2i32, 3, 5), 2);
+    assert_eq!(mod_pow(5, 0, 1), 0);
+    // 2^127 - 1 is prime.
+    let big_prime = i128::MAX;
+    assert_eq!(mod_pow(3, big_prime - 1, big_prime), 1);
+    assert_eq!(mod_inverse(3, 7), Some(5));
+    assert_eq!(mod_inverse(-3i64, 7), Some(2));
+    assert_eq!(mod_inverse(4u8, 6), None);
+    assert_eq!(mod_inverse(12345678901234567i128, big_prime).map(|x| mul_mod(x as u128, 12345678901234567, big_prime as u128)), Some(1));
+}
+
+/// A system of congruences without a solution, as reported by `crt`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InconsistentSystem {
+    /// The index of the first congruence that contradicts the ones before it.
+    pub index: usize,
+}
+
+impl fmt::Display for InconsistentSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "congruence {} contradicts the previous ones", self.index)
+    }
+}
+
+impl Error for InconsistentSystem {}
+
+/// Solves the system of congruences `x ≡ residue (mod modulus)` given as `(residue, modulus)`
+/// pairs. The moduli don't have to be pairwise coprime. Returns the smallest non-negative
+/// solution `x` and the least common multiple of the moduli, so that the solutions are exactly
+/// `x + k * lcm`.
+pub fn crt<T: Integer>(congruences: &[(T, T)]) -> Result<(T, T), InconsistentSystem> {
+    // The solutions so far are `solution + k * period`.
+    let (mut solution, mut period) = (0i128, 1i128);
+    for (index, &(residue, modulus)) in congruences.iter().enumerate() {
+        let m = positive_modulus(wide(modulus)) as i128;
+        let residue = wide(residue).rem_euclid(m);
+        let (gcd, s, _) = extended_gcd_wide(period, m);
+        let difference = residue - solution;
+        if difference % gcd != 0 {
+            return Err(InconsistentSystem { index });
+        }
+        // solution + period * k ≡ residue (mod m), so k ≡ difference / gcd * s (mod m / gcd).
+        let lcm = period / gcd * m;
+        let reduced = (m / gcd) as u128;
+        let k = mul_mod((difference / gcd).rem_euclid(reduced as i128) as u128, s.rem_euclid(reduced as i128) as u128, reduced);
+        solution = add_mod(solution as u128, mul_mod(period as u128 % lcm as u128, k, lcm as u128), lcm as u128) as i128;
+        period = lcm;
+    }
+    Ok((narrow(solution), narrow(period)))
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+    assert_eq!(crt(&[(1u64, 4), (3, 6)]), Ok((9, 12)));
+    assert_eq!(crt(&[(1, 4), (3, 6), (0, 5), (2, 4)]), Err(InconsistentSystem { index: 3 }));
+    assert_eq!(crt(&[(1, 4), (2, 6)]).unwrap_err().to_string(), "congruence 1 contradicts the previous ones");
+    assert_eq!(crt::<i32>(&[]), Ok((0, 1)));
+    assert_eq!(crt(&[(-1i64, 1_000_000_007), (-1, 998_244_353)]), Ok((998_244_359_987_710_470, 998_244_359_987_710_471)));
+}
+
+/// Baby-step giant-step: the smallest `x >= 0` such that `base^x ≡ target (mod modulus)`, if any.
+/// `base` must be coprime with `modulus`. Takes time and memory proportional to the square root
+/// of the modulus.
+pub fn discrete_log<T: Integer>(base: T, target: T, modulus: T) -> Option<T> {
+    let m = positive_modulus(wide(modulus));
+    let base = wide(base).rem_euclid(m as i128) as u128;
+    let target = wide(target).rem_euclid(m as i128) as u128;
+    let steps = (m as f64).sqrt().ceil() as u128 + 1;
+
+    let mut baby_steps = HashMap::new();
+    let mut power = 1 % m;
+    for j in 0..steps {
+        baby_steps.entry(power).or_insert(j);
+        power = mul_mod(power, base, m);
+    }
+
+    let giant_step = pow_mod(mod_inverse(base as i128, m as i128)? as u128, steps, m);
+    let mut value = target;
+    for i in 0..steps {
+        if let Some(&j) = baby_steps.get(&value) {
+            return Some(narrow((i * steps + j) as i128));
+        }
+        value = mul_mod(value, giant_step, m);
+    }
+    None
+}
+
+#[test]
+fn test_discrete_log() {
+    assert_eq!(discrete_log(7u64, 5764801, 20201227), Some(8));
+    assert_eq!(discrete_log(7u64, 17807724, 20201227), Some(11));
+    assert_eq!(discrete_log(2, 1, 11), Some(0));
+    // 2 only generates the squares modulo 7.
+    assert_eq!(discrete_log(2, 3, 7), None);
+    assert_eq!(discrete_log(3i64, 13, 17), Some(4));
+}
@@ -0,0 +1,216 @@
+//! Combinators for parsing puzzle input, which report errors at the line and column where they
+//! occur instead of panicking.
+//!
+//! Each combinator hands slices of its input to a parser function. Errors returned by that
+//! function are relative to the slice it was given, and are moved to the corresponding position in
+//! the combinator's own input, so they can be nested freely:
+//!
+//! ```
+//! use aoc::parse;
+//!
+//! let groups: Vec<Vec<u32>> = parse::blocks("1\n2\n\n3\n", parse::numbers).unwrap();
+//! assert_eq!(groups, vec![vec![1, 2], vec![3]]);
+//! let err = parse::blocks::<Vec<u32>, Vec<_>, _>("1\n2\n\n3\nx4\n", parse::numbers).unwrap_err();
+//! assert_eq!(err.to_string(), "line 5, column 1: invalid value \"x4\": invalid digit found in string");
+//! ```
+
+use crate::{Grid, ParseError};
+use regex::Regex;
+use std::fmt::Display;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+/// Parses the entire string with `FromStr`.
+pub fn value<T>(s: &str) -> Result<T>
+    where T: FromStr, T::Err: Display
+{
+    s.parse().map_err(|err| ParseError::new(1, 1, format!("invalid value {:?}: {}", s, err)))
+}
+
+/// Parses each line with `f`.
+pub fn lines<'a, T, C, F>(input: &'a str, mut f: F) -> Result<C>
+    where C: FromIterator<T>, F: FnMut(&'a str) -> Result<T>
+{
+    input.lines().map(|line| f(line).map_err(|err| err.within(input, line))).collect()
+}
+
+/// Parses each block of lines with `f`. Blocks are separated by one or more blank lines, and do not
+/// include the trailing newline.
+pub fn blocks<'a, T, C, F>(input: &'a str, mut f: F) -> Result<C>
+    where C: FromIterator<T>, F: FnMut(&'a str) -> Result<T>
+{
+    input
+        .trim_end_matches('\n')
+        .split("\n\n")
+        .map(|block| block.trim_start_matches('\n'))
+        .filter(|block| !block.is_empty())
+        .map(|block| f(block).map_err(|err| err.within(input, block)))
+        .collect()
+}
+
+/// Parses one value per line, typically a list of numbers.
+pub fn numbers<T, C>(input: &str) -> Result<C>
+    where T: FromStr, T::Err: Display, C: FromIterator<T>
+{
+    lines(input, value)
+}
+
+/// Parses each item of a list like `1,2,3` with `f`. Whitespace around the items is ignored.
+pub fn separated<'a, T, C, F>(input: &'a str, separator: &str, mut f: F) -> Result<C>
+    where C: FromIterator<T>, F: FnMut(&'a str) -> Result<T>
+{
+    input
+        .trim_end()
+        .split(separator)
+        .map(|item| {
+            let item = item.trim();
+            f(item).map_err(|err| err.within(input, item))
+        })
+        .collect()
+}
+
+/// Parses each item of a list like `1,2,3` with `FromStr`.
+pub fn list<T, C>(input: &str, separator: &str) -> Result<C>
+    where T: FromStr, T::Err: Display, C: FromIterator<T>
+{
+    separated(input, separator, value)
+}
+
+/// Splits the string at the first occurrence of `separator`.
+pub fn split_once<'a>(s: &'a str, separator: &str) -> Result<(&'a str, &'a str)> {
+    match s.find(separator) {
+        Some(i) => Ok((&s[..i], &s[i + separator.len()..])),
+        None => Err(ParseError::at(s, &s[s.len()..], format!("expected {:?}", separator))),
+    }
+}
+
+/// Parses a rectangular grid of characters, converting each one with `f`. Characters for which
+/// `f` returns `None` are reported as errors, and so are rows of different lengths.
+pub fn grid<T, F>(input: &str, mut f: F) -> Result<Grid<T>>
+    where F: FnMut(char) -> Option<T>
+{
+    let mut width = None;
+    let rows = lines::<_, Vec<_>, _>(input, |line| {
+        let row = line
+            .chars()
+            .enumerate()
+            .map(|(x, c)| f(c).ok_or_else(|| ParseError::new(1, x + 1, format!("unexpected character {:?}", c))))
+            .collect::<Result<Vec<_>>>()?;
+        match width {
+            None => width = Some(row.len()),
+            Some(width) if width != row.len() =>
+                return Err(ParseError::new(1, 1, format!("expected a row of {} characters, found {}", width, row.len()))),
+            _ => {},
+        }
+        Ok(row)
+    })?;
+    let height = rows.len() as i64;
+    Ok(Grid::from_vec(width.unwrap_or(0) as i64, height, rows.into_iter().flatten().collect()))
+}
+
+/// Matches the regular expression against the string, and returns the captured groups. The
+/// regular expression should usually be anchored with `^` and `$`.
+pub fn captures<'a>(re: &Regex, s: &'a str) -> Result<Captures<'a>> {
+    match re.captures(s) {
+        Some(captures) => Ok(Captures { input: s, captures }),
+        None => Err(ParseError::new(1, 1, format!("expected a line matching {}, found {:?}", re, s))),
+    }
+}
+
+/// Groups captured by `captures`, whose errors point at the group in question.
+pub struct Captures<'a> {
+    input: &'a str,
+    captures: regex::Captures<'a>,
+}
+
+impl<'a> Captures<'a> {
+    /// Returns the text of the given group.
+    pub fn str(&self, group: usize) -> Result<&'a str> {
+        self.captures
+            .get(group)
+            .map(|m| m.as_str())
+            .ok_or_else(|| ParseError::new(1, 1, format!("group {} did not match", group)))
+    }
+
+    /// Parses the text of the given group with `FromStr`.
+    pub fn get<T>(&self, group: usize) -> Result<T>
+        where T: FromStr, T::Err: Display
+    {
+        self.parse(group, value)
+    }
+
+    /// Parses the text of the given group with `f`.
+    pub fn parse<T, F>(&self, group: usize, f: F) -> Result<T>
+        where F: FnOnce(&'a str) -> Result<T>
+    {
+        let s = self.str(group)?;
+        f(s).map_err(|err| err.within(self.input, s))
+    }
+}
+
+#[test]
+fn test_lines() {
+    assert_eq!(numbers::<u32, Vec<_>>("1\n2\n3\n"), Ok(vec![1, 2, 3]));
+    assert_eq!(numbers::<u32, Vec<_>>(""), Ok(vec![]));
+    assert_eq!(
+        numbers::<u8, Vec<_>>("1\n256\n"),
+        Err(ParseError::new(2, 1, "invalid value \"256\": number too large to fit in target type")));
+    assert_eq!(
+        lines::<_, Vec<(&str, u32)>, _>("a=1\nb=2\nc 3\n", |line| {
+            let (key, value) = split_once(line, "=")?;
+            Ok((key, self::value(value).map_err(|err| err.within(line, value))?))
+        }),
+        Err(ParseError::new(3, 4, "expected \"=\"")));
+}
+
+#[test]
+fn test_blocks() {
+    let input = "a\nb\n\nc\n\n\n\nd\ne\n";
+    assert_eq!(blocks::<_, Vec<_>, _>(input, Ok), Ok(vec!["a\nb", "c", "d\ne"]));
+    assert_eq!(blocks::<_, Vec<_>, _>(input, |block| Ok(block.lines().count())), Ok(vec![2, 1, 2]));
+    assert_eq!(
+        blocks::<(), Vec<_>, _>(input, |block| Err(ParseError::at(block, &block[block.len() - 1..], "bad"))),
+        Err(ParseError::new(2, 1, "bad")));
+    assert_eq!(
+        blocks::<Vec<u32>, Vec<_>, _>("1\n2\n\n3\n4x\n", numbers),
+        Err(ParseError::new(5, 1, "invalid value \"4x\": invalid digit found in string")));
+}
+
+#[test]
+fn test_list() {
+    assert_eq!(list::<i32, Vec<_>>("1,-2, 3\n", ","), Ok(vec![1, -2, 3]));
+    assert_eq!(
+        list::<i32, Vec<_>>("1,2,x,4", ","),
+        Err(ParseError::new(1, 5, "invalid value \"x\": invalid digit found in string")));
+    assert_eq!(
+        separated::<_, Vec<_>, _>("7,13,x", ",", |item| if item == "x" { Ok(None) } else { value(item).map(Some) }),
+        Ok(vec![Some(7u32), Some(13), None]));
+}
+
+#[test]
+fn test_grid() {
+    let to_bool = |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    };
+    assert_eq!(grid("#.\n.#\n", to_bool), Ok(Grid::from_vec(2, 2, vec![true, false, false, true])));
+    assert_eq!(grid("#.\n.x\n", to_bool), Err(ParseError::new(2, 2, "unexpected character 'x'")));
+    assert_eq!(grid("#.\n.\n", to_bool), Err(ParseError::new(2, 1, "expected a row of 2 characters, found 1")));
+}
+
+#[test]
+fn test_captures() {
+    let re = Regex::new(r"^(\d+)-(\d+) (.): (.*)$").unwrap();
+    let caps = captures(&re, "1-3 a: abcde").unwrap();
+    assert_eq!((caps.get(1), caps.get(2), caps.get(3), caps.str(4)), (Ok(1), Ok(3), Ok('a'), Ok("abcde")));
+    let caps = captures(&re, "1-99999999999 a: abcde").unwrap();
+    assert_eq!(
+        caps.get::<u32>(2),
+        Err(ParseError::new(1, 3, "invalid value \"99999999999\": number too large to fit in target type")));
+    assert_eq!(
+        lines::<_, Vec<u32>, _>("1-3 a: a\n1 a: b\n", |line| captures(&re, line)?.get(1)),
+        Err(ParseError::new(2, 1, "expected a line matching ^(\\d+)-(\\d+) (.): (.*)$, found \"1 a: b\"")));
+}
@@ -0,0 +1,221 @@
+//! Puzzle descriptions, converted to Markdown so they can be read offline.
+
+use crate::examples::{self, Example};
+use kuchiki::NodeRef;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub fn file_name(year: u32, day: u32) -> String {
+    format!("puzzles/{}/{:02}.md", year, day)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The description of part one, and of part two if it has been unlocked, as Markdown.
+    pub description: String,
+    pub examples: Vec<Example>,
+}
+
+impl Puzzle {
+    /// Extracts the description and examples from the puzzle page, or returns `None` if the page
+    /// contains no description.
+    pub fn from_html(root: &NodeRef) -> Option<Puzzle> {
+        root.select_first("article.day-desc").ok()?;
+        Some(Puzzle { description: to_markdown(root), examples: examples::extract(root) })
+    }
+
+    pub fn write_description(&self, year: u32, day: u32) -> io::Result<()> {
+        let file_name = file_name(year, day);
+        fs::create_dir_all(Path::new(&file_name).parent().unwrap())?;
+        fs::write(&file_name, &self.description)
+    }
+}
+
+/// Whether the description includes part two, which is only shown once part one is solved.
+pub fn has_part_two(description: &str) -> bool {
+    description.lines().any(|line| line.starts_with("## ") && line.contains("Part Two"))
+}
+
+/// Converts the `<article class="day-desc">` sections of the page to Markdown.
+pub fn to_markdown(root: &NodeRef) -> String {
+    let articles = root.select("article.day-desc").unwrap()
+        .map(|article| blocks(article.as_node()).trim().to_string())
+        .collect::<Vec<_>>();
+    articles.join("\n\n") + "\n"
+}
+
+fn name(node: &NodeRef) -> Option<String> {
+    node.as_element().map(|element| element.name.local.to_string())
+}
+
+fn attribute(node: &NodeRef, attribute: &str) -> Option<String> {
+    node.as_element()?.attributes.borrow().get(attribute).map(str::to_string)
+}
+
+/// Renders the children of a block-level element, each block separated by a blank line.
+fn blocks(node: &NodeRef) -> String {
+    let mut out = String::new();
+    for child in node.children() {
+        let block = match name(&child).as_deref() {
+            Some("h2") => format!("## {}", inline(&child).trim()),
+            Some("p") => inline(&child).trim().to_string(),
+            Some("pre") => {
+                let text = child.text_contents();
+                let fence = fence_for(&text, "```");
+                format!("{}\n{}{}{}", fence, text, if text.ends_with('\n') { "" } else { "\n" }, fence)
+            },
+            Some("ul") | Some("ol") => list(&child, name(&child).as_deref() == Some("ol")),
+            Some("blockquote") => blocks(&child).trim().lines().map(|line| format!("> {}", line).trim_end().to_string()).collect::<Vec<_>>().join("\n"),
+            _ => inline(&child).trim().to_string(),
+        };
+        if !block.is_empty() {
+            out += &block;
+            out += "\n\n";
+        }
+    }
+    out
+}
+
+fn list(node: &NodeRef, ordered: bool) -> String {
+    node.children()
+        .filter(|child| name(child).as_deref() == Some("li"))
+        .enumerate()
+        .map(|(i, item)| {
+            let marker = if ordered { format!("{}. ", i + 1) } else { "- ".to_string() };
+            let indent = " ".repeat(marker.len());
+            let body = if item.children().any(|child| matches!(name(&child).as_deref(), Some("p") | Some("ul") | Some("ol") | Some("pre"))) {
+                blocks(&item)
+            } else {
+                inline(&item)
+            };
+            let body = body.trim().lines().collect::<Vec<_>>().join(&format!("\n{}", indent));
+            marker + &body
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders inline content, collapsing whitespace the way HTML does.
+fn inline(node: &NodeRef) -> String {
+    let mut out = String::new();
+    for child in node.children() {
+        if let Some(text) = child.as_text() {
+            out += &escape(&collapse_whitespace(&text.borrow()));
+            continue;
+        }
+        match name(&child).as_deref() {
+            Some("code") => {
+                let text = collapse_whitespace(&child.text_contents());
+                let fence = fence_for(&text, "`");
+                let padding = if text.starts_with('`') || text.ends_with('`') { " " } else { "" };
+                let code = format!("{}{}{}{}{}", fence, padding, text, padding, fence);
+                // Markdown can't emphasize part of a code span, so emphasize all of it.
+                let emphasized = child.descendants().any(|descendant| name(&descendant).as_deref() == Some("em"));
+                out += &if emphasized { format!("**{}**", code) } else { code };
+            },
+            Some("em") | Some("strong") => {
+                let text = inline(&child);
+                if !text.trim().is_empty() {
+                    out += &format!("**{}**", text.trim());
+                }
+            },
+            Some("a") => match attribute(&child, "href") {
+                Some(href) => out += &format!("[{}]({})", inline(&child).trim(), href),
+                None => out += &inline(&child),
+            },
+            Some("br") => out += "  \n",
+            Some("script") | Some("style") => {},
+            _ => out += &inline(&child),
+        }
+    }
+    out
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_was_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_was_space {
+                out.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            out.push(c);
+            last_was_space = false;
+        }
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_[]<>".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Returns a fence made of the given character that is longer than any run of it in the text.
+fn fence_for(text: &str, min: &str) -> String {
+    let c = min.chars().next().unwrap();
+    let longest_run = text.split(|ch| ch != c).map(str::len).max().unwrap_or(0);
+    c.to_string().repeat(std::cmp::max(min.len(), longest_run + 1))
+}
+
+#[test]
+fn test_to_markdown() {
+    use kuchiki::parse_html;
+    use kuchiki::traits::TendrilSink;
+
+    let root = parse_html().one("\
+        <main>\
+        <article class=\"day-desc\"><h2>--- Day 1: Report Repair ---</h2>\
+        <p>After saving Christmas <a href=\"/2019\" target=\"_blank\">five years in a row</a>, \
+        you've decided to take a <span title=\"Easter egg\">vacation</span>.\n   To do that, \
+        you need <em class=\"star\">fifty stars</em> by December 25th.</p>\
+        <ul><li>Use <code>a*b</code>.</li><li>Don't use <code>`</code>.</li></ul>\
+        <pre><code>1721\n<em>979</em>\n</code></pre>\
+        <p>The correct answer is <code><em>514579</em></code>; <code>2 * 3</code> is not.</p>\
+        </article>\
+        <p>Your puzzle answer was <code>1005459</code>.</p>\
+        <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+        <p>Find <em>three</em> entries.</p>\
+        </article>\
+        </main>");
+    let markdown = to_markdown(&root);
+    assert_eq!(markdown, "\
+## --- Day 1: Report Repair ---
+
+After saving Christmas [five years in a row](/2019), you've decided to take a vacation. To do that, \
+you need **fifty stars** by December 25th.
+
+- Use `a*b`.
+- Don't use `` ` ``.
+
+```
+1721
+979
+```
+
+The correct answer is **`514579`**; `2 * 3` is not.
+
+## --- Part Two ---
+
+Find **three** entries.
+");
+    assert!(has_part_two(&markdown));
+    assert!(!has_part_two(&markdown[..markdown.find("## --- Part Two").unwrap()]));
+    assert!(!markdown.contains("1005459"));
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(escape("a*b_c [d] <e> `f` \\"), "a\\*b\\_c \\[d\\] \\<e\\> \\`f\\` \\\\");
+    assert_eq!(fence_for("a ``b`` c", "`"), "```");
+    assert_eq!(fence_for("plain", "```"), "```");
+}
@@ -0,0 +1,73 @@
+//! A small, seeded pseudo-random number generator for generating test inputs.
+//!
+//! It implements SplitMix64, so the same seed produces the same sequence on every platform and
+//! with every version of this crate, which keeps generated inputs reproducible.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed number in the given range, which must not be empty.
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(range.start < range.end, "empty range {:?}", range);
+        let size = (range.end as i128 - range.start as i128) as u128;
+        // Multiply-shift, which is close enough to uniform for ranges much smaller than 2^64.
+        let offset = (self.next_u64() as u128 * size) >> 64;
+        (range.start as i128 + offset as i128) as i64
+    }
+
+    /// A uniformly distributed index below `len`, which must be positive.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..len as i64) as usize
+    }
+
+    /// True with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    /// A uniformly chosen element of the slice, which must not be empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Shuffles the slice in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[test]
+fn test_rng() {
+    let mut rng = Rng::new(1234567);
+    assert_eq!(rng.next_u64(), 6457827717110365317);
+    assert_eq!(rng.next_u64(), 3203168211198807973);
+    let mut rng = Rng::new(42);
+    for _ in 0..1000 {
+        assert!((-3..5).contains(&rng.range(-3..5)));
+    }
+    assert_eq!(rng.range(i64::MIN..i64::MIN + 1), i64::MIN);
+    let mut items = (0..10).collect::<Vec<_>>();
+    rng.shuffle(&mut items);
+    assert_ne!(items, (0..10).collect::<Vec<_>>());
+    items.sort_unstable();
+    assert_eq!(items, (0..10).collect::<Vec<_>>());
+}
@@ -0,0 +1,149 @@
+//! Scaffolding for a new day: a skeleton solution in `src/dayDD.rs`, registered in `src/lib.rs`.
+
+use regex::Regex;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// A solution that parses the input into lines and doesn't solve either part yet, with tests that
+/// only check that the example and the real input parse.
+pub fn solution_source(day: u32) -> String {
+    format!("\
+use aoc::{{parse, ParseError, Solution}};
+
+fn parse(input: &str) -> Result<Vec<String>, ParseError> {{
+    parse::lines(input, |line| Ok(line.to_string()))
+}}
+
+fn part1(_lines: &[String]) -> Result<u64, &'static str> {{
+    Err(\"not implemented\")
+}}
+
+fn part2(_lines: &[String]) -> Result<u64, &'static str> {{
+    Err(\"not implemented\")
+}}
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = {day};
+
+    type Input = Vec<String>;
+    type Output1 = Result<u64, &'static str>;
+    type Output2 = Result<u64, &'static str>;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {{
+        parse(input)
+    }}
+
+    fn part1(input: &Vec<String>) -> Result<u64, &'static str> {{
+        part1(input)
+    }}
+
+    fn part2(input: &Vec<String>) -> Result<u64, &'static str> {{
+        part2(input)
+    }}
+}}
+
+aoc::solution_tests! {{
+    Day{day:02},
+    example_0 {{}},
+    input {{}},
+}}
+", day = day)
+}
+
+/// Inserts `line` among the lines that match `re`, whose first capture is a day number, keeping
+/// them sorted by day and copying their indentation.
+fn insert_sorted(contents: &str, re: &Regex, day: u32, line: &str) -> Option<String> {
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    let matches = lines.iter().enumerate()
+        .filter_map(|(i, line)| Some((i, re.captures(line)?[1].parse::<u32>().ok()?)))
+        .collect::<Vec<_>>();
+    let &(last_index, _) = matches.last()?;
+    let index = matches.iter().find(|&&(_, other_day)| other_day > day).map_or(last_index + 1, |&(i, _)| i);
+    let indent = &lines[last_index][..lines[last_index].len() - lines[last_index].trim_start().len()];
+    let line = format!("{}{}", indent, line);
+    lines.insert(index, &line);
+    Some(lines.join("\n") + if contents.ends_with('\n') { "\n" } else { "" })
+}
+
+/// Writes a skeleton solution for the given day into `src/dayDD.rs` under `root`, and registers it
+/// in `src/lib.rs`. Fails without changing anything if the solution already exists. Returns the
+/// name of the new file.
+pub fn add_day(root: &Path, day: u32) -> io::Result<String> {
+    let lib_file_name = root.join("src/lib.rs");
+    let lib = fs::read_to_string(&lib_file_name)?;
+    let module = format!("day{:02}", day);
+    if Regex::new(&format!(r"\b{}\b", module)).unwrap().is_match(&lib) {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already mentions {}", lib_file_name.display(), module)));
+    }
+    let invalid_lib = || io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("could not find where to add {} in {}", module, lib_file_name.display()));
+    let lib = insert_sorted(&lib, &Regex::new(r"^\s*pub mod day(\d+);$").unwrap(), day, &format!("pub mod {};", module))
+        .ok_or_else(invalid_lib)?;
+    let lib = insert_sorted(
+        &lib, &Regex::new(r"^\s*registry\.register::<day(\d+)::Day\d+>\(\);$").unwrap(), day,
+        &format!("registry.register::<{}::Day{:02}>();", module, day))
+        .ok_or_else(invalid_lib)?;
+
+    let file_name = root.join(format!("src/{}.rs", module));
+    OpenOptions::new().write(true).create_new(true).open(&file_name)?
+        .write_all(solution_source(day).as_bytes())?;
+    fs::write(&lib_file_name, lib)?;
+    Ok(file_name.to_string_lossy().into_owned())
+}
+
+#[test]
+fn test_add_day() {
+    let root = crate::testing::temp_dir("scaffold-add-day");
+    fs::create_dir(root.join("src")).unwrap();
+    fs::write(root.join("src/lib.rs"), "\
+pub mod day01;
+pub mod day03;
+
+pub fn registry() -> aoc::Registry {
+    let mut registry = aoc::Registry::new(YEAR);
+    registry.register::<day01::Day01>();
+    registry.register::<day03::Day03>();
+    registry
+}
+").unwrap();
+
+    let file_name = add_day(&root, 2).unwrap();
+    assert_eq!(file_name, root.join("src/day02.rs").to_string_lossy());
+    assert_eq!(fs::read_to_string(&file_name).unwrap(), solution_source(2));
+    add_day(&root, 4).unwrap();
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), "\
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+
+pub fn registry() -> aoc::Registry {
+    let mut registry = aoc::Registry::new(YEAR);
+    registry.register::<day01::Day01>();
+    registry.register::<day02::Day02>();
+    registry.register::<day03::Day03>();
+    registry.register::<day04::Day04>();
+    registry
+}
+");
+
+    // Existing solutions are left alone, whether they are registered or not.
+    fs::write(&file_name, "// solved").unwrap();
+    assert_eq!(add_day(&root, 2).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+    assert_eq!(fs::read_to_string(&file_name).unwrap(), "// solved");
+    fs::write(root.join("src/day05.rs"), "// solved").unwrap();
+    let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+    assert_eq!(add_day(&root, 5).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(), lib);
+
+    fs::write(root.join("src/lib.rs"), "pub mod utils;\n").unwrap();
+    assert_eq!(add_day(&root, 6).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert!(!root.join("src/day06.rs").exists());
+}
@@ -0,0 +1,198 @@
+//! Finding the session cookie to authenticate with, and checking that it is still valid.
+//!
+//! The cookie for the selected profile (`AOC_PROFILE`, or `default`) is taken from the first of:
+//!
+//! - the `AOC_SESSION` environment variable,
+//! - the file `.session_cookie` in the current directory (default profile only),
+//! - the per-user config file, which has one `<profile> <cookie>` pair per line,
+//! - an interactive prompt, if standard input is a terminal. The answer is saved to the config
+//!   file.
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PROFILE: &str = "default";
+pub const LOCAL_FILE_NAME: &str = ".session_cookie";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub profile: String,
+    pub cookie: String,
+    /// Where the cookie was found, for diagnostics.
+    pub source: String,
+}
+
+#[derive(Debug)]
+pub struct NoSessionError {
+    profile: String,
+}
+
+impl fmt::Display for NoSessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no session cookie found for profile {}; set AOC_SESSION, or add a line \"{} <cookie>\" to ", self.profile, self.profile)?;
+        match config_file_name() {
+            Some(file_name) => write!(f, "{}", file_name.display()),
+            None => write!(f, "the config file"),
+        }
+    }
+}
+
+impl Error for NoSessionError {}
+
+pub fn profile() -> String {
+    env::var("AOC_PROFILE").unwrap_or_else(|_| DEFAULT_PROFILE.to_string())
+}
+
+/// The per-user config file: `$AOC_CONFIG_DIR/sessions`, or `aoc/sessions` in the platform's
+/// config directory.
+pub fn config_file_name() -> Option<PathBuf> {
+    let env_dir = |name| env::var_os(name).filter(|dir| !dir.is_empty()).map(PathBuf::from);
+    let dir = env_dir("AOC_CONFIG_DIR")
+        .or_else(|| env_dir("XDG_CONFIG_HOME").map(|dir| dir.join("aoc")))
+        .or_else(|| env_dir("APPDATA").map(|dir| dir.join("aoc")))
+        .or_else(|| env_dir("HOME").map(|dir| dir.join(".config").join("aoc")))?;
+    Some(dir.join("sessions"))
+}
+
+/// Looks up the session for the current profile, prompting for it only if there is a terminal to
+/// prompt on.
+pub fn load() -> Result<Session, Box<dyn Error>> {
+    let profile = profile();
+    let config_file_name = config_file_name();
+    if let Some(session) = find(&profile, env::var("AOC_SESSION").ok(), Path::new(LOCAL_FILE_NAME), config_file_name.as_deref())? {
+        return Ok(session);
+    }
+    match config_file_name {
+        Some(config_file_name) if stdin_is_terminal() => {
+            println!(
+                "No session cookie found for profile {}. Please log in to https://adventofcode.com/ in your browser, open the browser console, copy the value of the 'session' cookie, and paste it here:",
+                profile);
+            let mut line = String::new();
+            io::stdin().lock().read_line(&mut line)?;
+            let cookie = line.trim().to_string();
+            save(&config_file_name, &profile, &cookie)?;
+            println!("Saved session cookie to {}", config_file_name.display());
+            Ok(Session { profile, cookie, source: config_file_name.display().to_string() })
+        },
+        _ => Err(NoSessionError { profile }.into()),
+    }
+}
+
+fn find(profile: &str, env_session: Option<String>, local_file_name: &Path, config_file_name: Option<&Path>) -> io::Result<Option<Session>> {
+    let session = |cookie: &str, source: String| Some(Session { profile: profile.to_string(), cookie: cookie.trim().to_string(), source });
+    if let Some(cookie) = env_session.filter(|cookie| !cookie.trim().is_empty()) {
+        return Ok(session(&cookie, "AOC_SESSION".to_string()));
+    }
+    if profile == DEFAULT_PROFILE {
+        if let Some(cookie) = read_if_exists(local_file_name)?.filter(|cookie| !cookie.trim().is_empty()) {
+            return Ok(session(&cookie, local_file_name.display().to_string()));
+        }
+    }
+    if let Some(config_file_name) = config_file_name {
+        if let Some(cookie) = read_if_exists(config_file_name)?.and_then(|contents| lookup(&contents, profile)) {
+            return Ok(session(&cookie, config_file_name.display().to_string()));
+        }
+    }
+    Ok(None)
+}
+
+fn read_if_exists(file_name: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(file_name) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Finds the cookie for the given profile in the contents of a config file.
+fn lookup(contents: &str, profile: &str) -> Option<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some((fields.next()?, fields.next()?))
+        })
+        .find(|&(name, _)| name == profile)
+        .map(|(_, cookie)| cookie.to_string())
+}
+
+fn save(config_file_name: &Path, profile: &str, cookie: &str) -> io::Result<()> {
+    if let Some(dir) = config_file_name.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(config_file_name)?;
+    writeln!(file, "{} {}", profile, cookie)
+}
+
+#[cfg(unix)]
+fn stdin_is_terminal() -> bool {
+    unsafe { libc::isatty(libc::STDIN_FILENO) != 0 }
+}
+
+#[cfg(not(unix))]
+fn stdin_is_terminal() -> bool {
+    true
+}
+
+#[test]
+fn test_find() {
+    let dir = crate::testing::temp_dir("session-find");
+    let local = dir.join("local");
+    let config = dir.join("sessions");
+    let find = |profile, env_session: Option<&str>| {
+        find(profile, env_session.map(str::to_string), &local, Some(&config))
+            .unwrap()
+            .map(|session| (session.cookie, session.source))
+    };
+    let source = |path: &Path| path.display().to_string();
+
+    assert_eq!(find("default", None), None);
+    assert_eq!(find("default", Some("from-env")), Some(("from-env".to_string(), "AOC_SESSION".to_string())));
+
+    save(&config, "default", "from-config").unwrap();
+    save(&config, "alice", "alice-cookie").unwrap();
+    fs::write(&config, fs::read_to_string(&config).unwrap() + "# bob commented out\n").unwrap();
+    assert_eq!(find("default", None), Some(("from-config".to_string(), source(&config))));
+    assert_eq!(find("alice", None), Some(("alice-cookie".to_string(), source(&config))));
+    assert_eq!(find("bob", None), None);
+
+    fs::write(&local, "from-local\n").unwrap();
+    assert_eq!(find("default", None), Some(("from-local".to_string(), source(&local))));
+    assert_eq!(find("alice", None), Some(("alice-cookie".to_string(), source(&config))));
+    assert_eq!(find("alice", Some("from-env")), Some(("from-env".to_string(), "AOC_SESSION".to_string())));
+}
+
+/// Checks that the cookie is accepted by the website, by requesting the settings page, which is
+/// only available when logged in.
+pub fn check(base_url: &str, cookie: &str) -> Result<(), Box<dyn Error>> {
+    let url = format!("{}/settings", base_url);
+    let mut response = reqwest::Client::new()
+        .get(&url)
+        .header(reqwest::header::COOKIE, format!("session={}", cookie))
+        .send()?;
+    let text = response.text()?;
+    let logged_in = response.status().is_success() &&
+        response.url().path().ends_with("/settings") &&
+        !text.contains("[Log In]") &&
+        !text.contains("please identify yourself");
+    if logged_in {
+        Ok(())
+    } else {
+        Err("session cookie expired or invalid; log in again and update it".into())
+    }
+}
+
+#[test]
+fn test_check() {
+    let server = crate::testing::mock_aoc_server();
+    assert!(check(&server.base_url, crate::testing::MOCK_SESSION_COOKIE).is_ok());
+    let error = check(&server.base_url, "expired").unwrap_err().to_string();
+    assert!(error.contains("expired"), "{}", error);
+    assert_eq!(server.requests()[0].path, "/settings");
+}
@@ -0,0 +1,272 @@
+//! Tests generated from a manifest of expected answers, with the `solution_tests!` macro.
+//!
+//! Each entry in the manifest becomes a test that parses one input and checks the given parts.
+//! An entry named `input` uses the real input, and one named `example_N` uses example `N`. The
+//! answer to a part of an example can be left out, to check the answer that the puzzle description
+//! gives in `examples/YYYY/DD-N.answers`; if both are there, they must agree:
+//!
+//! ```no_run
+//! # use aoc::{parse, ParseError, Solution};
+//! # pub struct Day01;
+//! # impl Solution for Day01 {
+//! #     const YEAR: u32 = 2020;
+//! #     const DAY: u32 = 1;
+//! #     type Input = Vec<u64>;
+//! #     type Output1 = u64;
+//! #     type Output2 = u64;
+//! #     fn parse(input: &str) -> Result<Vec<u64>, ParseError> { parse::numbers(input) }
+//! #     fn part1(input: &Vec<u64>) -> u64 { input.iter().sum() }
+//! #     fn part2(input: &Vec<u64>) -> u64 { input.iter().product() }
+//! # }
+//! aoc::solution_tests! {
+//!     Day01,
+//!     example_0 { 1, 2 => 241861950 },
+//!     input { 1 => 1005459, 2 => 92643264 },
+//! }
+//! ```
+//!
+//! The answers are compared as strings, so an expected value can be a string, or an integer of
+//! any size that formats the same way as the part's answer. The inputs are only read from disk: if
+//! the file is missing, the test is skipped instead of fetching it over the network.
+
+use crate::{example_file_name, examples, input_file_name, migrate_flat_layout_once, parse_input, Solution};
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Defines a `solution_tests` module with a test for each entry of the manifest. See the
+/// `solution_tests` module for the format.
+#[macro_export]
+macro_rules! solution_tests {
+    ($solution:ty, $($name:ident { $($part:literal $(=> $expected:expr)?),* $(,)? }),* $(,)?) => {
+        #[cfg(test)]
+        mod solution_tests {
+            use super::*;
+
+            $(
+                #[test]
+                fn $name() {
+                    $crate::solution_tests::check::<$solution>(
+                        stringify!($name),
+                        &[$(($part, vec![$($crate::solution_tests::expected($expected))?].pop())),*]);
+                }
+            )*
+        }
+    };
+}
+
+/// An expected answer in a manifest. Integers are `i128`, so that integer literals of any size are
+/// accepted without a suffix.
+pub trait Expected {
+    fn into_expected(self) -> String;
+}
+
+impl Expected for i128 {
+    fn into_expected(self) -> String {
+        self.to_string()
+    }
+}
+
+impl Expected for &str {
+    fn into_expected(self) -> String {
+        self.to_string()
+    }
+}
+
+/// Formats an expected answer for comparison.
+pub fn expected<T: Expected>(answer: T) -> String {
+    answer.into_expected()
+}
+
+#[test]
+fn test_expected() {
+    assert_eq!(expected(42313823813632), "42313823813632");
+    assert_eq!(expected(22 * 10), "220");
+    assert_eq!(expected("mxmxvkd,sqjhc,fvjkl"), "mxmxvkd,sqjhc,fvjkl");
+}
+
+/// The example that the test with the given name reads, or `None` for the real input.
+fn example_index(test_name: &str) -> Option<usize> {
+    if test_name == "input" {
+        return None;
+    }
+    match test_name.strip_prefix("example_").and_then(|index| index.parse().ok()) {
+        Some(index) => Some(index),
+        None => panic!("test name must be input or example_<index>, got {}", test_name),
+    }
+}
+
+/// The file that the test with the given name reads its input from.
+fn file_name(year: u32, day: u32, test_name: &str) -> String {
+    match example_index(test_name) {
+        Some(index) => example_file_name(year, day, index),
+        None => input_file_name(year, day),
+    }
+}
+
+#[test]
+fn test_file_name() {
+    assert_eq!(file_name(2020, 7, "input"), "inputs/2020/07.in");
+    assert_eq!(file_name(2020, 7, "example_12"), "examples/2020/07-12.example");
+}
+
+/// Reads the input of a test from disk, or returns `None` if the file is missing, in which case
+/// the test should be skipped.
+fn read_input(year: u32, test_name: &str, file_name: &str) -> Option<String> {
+    if let Err(err) = migrate_flat_layout_once(year) {
+        panic!("could not move the inputs and examples into per-year directories: {}", err);
+    }
+    match fs::read_to_string(file_name) {
+        Ok(input) => Some(input),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            eprintln!("Skipping {}: {} not found", test_name, file_name);
+            None
+        },
+        Err(err) => panic!("could not read {}: {}", file_name, err),
+    }
+}
+
+/// The `.answers` file of an example, if it exists. `example_answer` would fetch the puzzle if it
+/// doesn't, so tests check for the file first.
+fn answers_file_name<S: Solution>(index: usize) -> Option<String> {
+    Some(examples::answers_file_name(S::YEAR, S::DAY, index)).filter(|file_name| Path::new(file_name).exists())
+}
+
+/// Runs the test with the given name, checking each part against the answer in the manifest or,
+/// for examples, in the `.answers` file. Panics with the file name, the expected answer and the
+/// actual answer of every part that fails.
+pub fn check<S: Solution + 'static>(test_name: &str, manifest: &[(u32, Option<String>)]) {
+    let file_name = file_name(S::YEAR, S::DAY, test_name);
+    let input = match read_input(S::YEAR, test_name, &file_name) {
+        Some(input) => input,
+        None => return,
+    };
+    let index = example_index(test_name);
+    let answers_file_name = index.and_then(answers_file_name::<S>);
+    let (expected, mut failures) = expected_answers(
+        &file_name, answers_file_name.as_deref(), manifest, |part| S::example_answer(index.unwrap(), part));
+    failures.extend(self::failures::<S>(&file_name, &input, &expected));
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+/// Checks `solve` on an example against the answer to `part` in the example's `.answers` file,
+/// and skips the test like `check` if the example is missing. This is for solutions that the
+/// manifest can't express, such as ones with a parameter that differs for the examples.
+pub fn check_example<S, F, R>(index: usize, part: u32, solve: F)
+    where S: Solution, F: FnOnce(&str) -> R, R: Display
+{
+    let file_name = example_file_name(S::YEAR, S::DAY, index);
+    let input = match read_input(S::YEAR, &format!("example_{}", index), &file_name) {
+        Some(input) => input,
+        None => return,
+    };
+    let (expected, failures) = expected_answers(
+        &file_name, answers_file_name::<S>(index).as_deref(), &[(part, None)], |part| S::example_answer(index, part));
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    let actual = solve(&input).to_string();
+    assert!(actual == expected[0].1, "\n{}, part {}: expected {}, got {}", file_name, part, expected[0].1, actual);
+}
+
+/// Picks the answer to check for each part: the one in the manifest, or else the one that
+/// `example_answer` reads from `answers_file_name`. Returns the answers, and a failure for each
+/// part whose two answers disagree, or that has neither.
+fn expected_answers<F>(
+    file_name: &str, answers_file_name: Option<&str>, manifest: &[(u32, Option<String>)], example_answer: F,
+) -> (Vec<(u32, String)>, Vec<String>)
+    where F: Fn(u32) -> Option<String>
+{
+    let mut expected = Vec::new();
+    let mut failures = Vec::new();
+    for (part, manifest_answer) in manifest {
+        let example_answer = answers_file_name.and_then(|_| example_answer(*part));
+        match (manifest_answer, example_answer) {
+            (Some(manifest_answer), Some(example_answer)) if *manifest_answer != example_answer => failures.push(format!(
+                "{}, part {}: the manifest expects {}, but {} expects {}",
+                file_name, part, manifest_answer, answers_file_name.unwrap(), example_answer)),
+            (Some(answer), _) => expected.push((*part, answer.clone())),
+            (None, Some(answer)) => expected.push((*part, answer)),
+            (None, None) => failures.push(match answers_file_name {
+                Some(answers_file_name) => format!(
+                    "{}, part {}: no answer in the manifest or in {}", file_name, part, answers_file_name),
+                None => format!("{}, part {}: no answer in the manifest", file_name, part),
+            }),
+        }
+    }
+    (expected, failures)
+}
+
+#[test]
+fn test_expected_answers() {
+    let manifest = [(1, Some("4".to_string())), (2, None)];
+    let example_answer = |part| if part == 1 { Some("4".to_string()) } else { None };
+    assert_eq!(
+        expected_answers("07-0.example", Some("07-0.answers"), &manifest[..1], example_answer),
+        (vec![(1, "4".to_string())], vec![]));
+    assert_eq!(
+        expected_answers("07-0.example", Some("07-0.answers"), &[(1, None)], example_answer),
+        (vec![(1, "4".to_string())], vec![]));
+    assert_eq!(
+        expected_answers("07-0.example", Some("07-0.answers"), &[(1, Some("5".to_string()))], example_answer),
+        (vec![], vec!["07-0.example, part 1: the manifest expects 5, but 07-0.answers expects 4".to_string()]));
+    assert_eq!(
+        expected_answers("07-0.example", Some("07-0.answers"), &manifest, example_answer),
+        (vec![(1, "4".to_string())], vec!["07-0.example, part 2: no answer in the manifest or in 07-0.answers".to_string()]));
+    assert_eq!(
+        expected_answers("07.in", None, &manifest, example_answer),
+        (vec![(1, "4".to_string())], vec!["07.in, part 2: no answer in the manifest".to_string()]));
+}
+
+fn failures<S: Solution + 'static>(file_name: &str, input: &str, expected: &[(u32, String)]) -> Vec<String> {
+    let parsed = match parse_input::<S>(input) {
+        Ok(parsed) => parsed,
+        Err(err) => return vec![format!("{}: {}", file_name, err)],
+    };
+    expected.iter()
+        .filter_map(|(part, expected)| match parsed.run(*part) {
+            Ok(actual) if actual == *expected => None,
+            Ok(actual) => Some(format!("{}, part {}: expected {}, got {}", file_name, part, expected, actual)),
+            Err(err) => Some(format!("{}, part {}: expected {}, got error: {}", file_name, part, expected, err)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+struct Sum;
+
+#[cfg(test)]
+impl Solution for Sum {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 1;
+
+    type Input = Vec<u32>;
+    type Output1 = u32;
+    type Output2 = Result<u32, &'static str>;
+
+    fn parse(input: &str) -> Result<Vec<u32>, crate::ParseError> {
+        crate::parse::numbers(input)
+    }
+
+    fn part1(input: &Vec<u32>) -> u32 {
+        input.iter().sum()
+    }
+
+    fn part2(_input: &Vec<u32>) -> Result<u32, &'static str> {
+        Err("not implemented")
+    }
+}
+
+#[test]
+fn test_failures() {
+    let expected = |answers: &[(u32, &str)]| answers.iter().map(|&(part, answer)| (part, answer.to_string())).collect::<Vec<_>>();
+    assert!(failures::<Sum>("1.example", "1\n2\n", &expected(&[(1, "3")])).is_empty());
+    assert_eq!(
+        failures::<Sum>("1.example", "1\n2\n", &expected(&[(1, "4"), (2, "5")])),
+        vec![
+            "1.example, part 1: expected 4, got 3",
+            "1.example, part 2: expected 5, got error: not implemented",
+        ]);
+    assert_eq!(
+        failures::<Sum>("1.example", "1\nx\n", &expected(&[(1, "3")])),
+        vec!["1.example: invalid input at line 2, column 1: invalid value \"x\": invalid digit found in string"]);
+}
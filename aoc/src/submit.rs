@@ -0,0 +1,294 @@
+//! Submitting answers to the website, and keeping a local log of every attempt.
+
+use kuchiki::parse_html;
+use kuchiki::traits::TendrilSink;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub fn attempt_log_file_name(year: u32, day: u32, part: u32) -> String {
+    format!("answers/{}/{:02}-{}.attempts", year, day, part)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited { wait: Option<Duration> },
+    AlreadySolved,
+    Unrecognized(String),
+}
+
+impl Outcome {
+    fn to_log_str(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited { .. } => "rate-limited",
+            Outcome::AlreadySolved => "already-solved",
+            Outcome::Unrecognized(_) => "unrecognized",
+        }
+    }
+
+    fn from_log_str(s: &str) -> Option<Outcome> {
+        Some(match s {
+            "correct" => Outcome::Correct,
+            "too-high" => Outcome::TooHigh,
+            "too-low" => Outcome::TooLow,
+            "wrong" => Outcome::Wrong,
+            "rate-limited" => Outcome::RateLimited { wait: None },
+            "already-solved" => Outcome::AlreadySolved,
+            "unrecognized" => Outcome::Unrecognized(String::new()),
+            _ => return None,
+        })
+    }
+
+    fn is_rejection(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::TooHigh => write!(f, "That's not the right answer; it is too high."),
+            Outcome::TooLow => write!(f, "That's not the right answer; it is too low."),
+            Outcome::Wrong => write!(f, "That's not the right answer."),
+            Outcome::RateLimited { wait: Some(wait) } =>
+                write!(f, "You gave an answer too recently; wait {} s before trying again.", wait.as_secs()),
+            Outcome::RateLimited { wait: None } => write!(f, "You gave an answer too recently."),
+            Outcome::AlreadySolved => write!(f, "This part was already solved."),
+            Outcome::Unrecognized(text) => write!(f, "Unrecognized response: {}", text),
+        }
+    }
+}
+
+/// Determines the outcome of a submission from the HTML page that the website responds with.
+pub fn classify(html: &str) -> Outcome {
+    let root = parse_html().one(html);
+    let text = match root.select_first("article") {
+        Ok(article) => article.text_contents(),
+        Err(()) => root.text_contents(),
+    };
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited { wait: parse_wait(&text) }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unrecognized(text.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+}
+
+/// Parses the remaining wait time from text like "You have 1m 4s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    let mut secs = 0;
+    for token in text[start..end].split_whitespace() {
+        let (number, unit) = token.split_at(token.len() - 1);
+        let number = number.parse::<u64>().ok()?;
+        secs += match unit {
+            "h" => 3600 * number,
+            "m" => 60 * number,
+            "s" => number,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+#[test]
+fn test_classify() {
+    assert_eq!(
+        classify("<main><article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to saving your vacation.</p></article></main>"),
+        Outcome::Correct);
+    assert_eq!(
+        classify("<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. (You guessed <code>42</code>.)</p></article>"),
+        Outcome::TooHigh);
+    assert_eq!(
+        classify("<article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article>"),
+        Outcome::TooLow);
+    assert_eq!(
+        classify("<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>"),
+        Outcome::Wrong);
+    assert_eq!(
+        classify("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 4s left to wait. <a href=\"/2020/day/7\">[Return to Day 7]</a></p></article>"),
+        Outcome::RateLimited { wait: Some(Duration::from_secs(64)) });
+    assert_eq!(
+        classify("<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2020/day/7\">[Return to Day 7]</a></p></article>"),
+        Outcome::AlreadySolved);
+    assert_eq!(classify("<p>Something\n  else</p>"), Outcome::Unrecognized("Something else".to_string()));
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub timestamp: u64,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+/// All attempts made for one part of a puzzle, stored one per line as tab-separated timestamp,
+/// outcome and answer.
+pub struct AttemptLog {
+    file_name: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl AttemptLog {
+    pub fn load(file_name: impl AsRef<Path>) -> io::Result<AttemptLog> {
+        let file_name = file_name.as_ref().to_path_buf();
+        let contents = match fs::read_to_string(&file_name) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        let attempts = contents
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                Some(Attempt {
+                    timestamp: fields.next()?.parse().ok()?,
+                    outcome: Outcome::from_log_str(fields.next()?)?,
+                    answer: fields.next()?.to_string(),
+                })
+            })
+            .collect();
+        Ok(AttemptLog { file_name, attempts })
+    }
+
+    /// Returns an error explaining why the answer should not be submitted, if it is already known
+    /// to be wrong from earlier attempts.
+    pub fn check(&self, answer: &str) -> Result<(), String> {
+        for attempt in &self.attempts {
+            if attempt.outcome == Outcome::Correct {
+                return Err(format!("answer {} was already accepted", attempt.answer));
+            }
+            if attempt.outcome.is_rejection() && attempt.answer == answer {
+                return Err(format!("answer {} was already rejected: {}", answer, attempt.outcome));
+            }
+        }
+        if let Ok(number) = answer.parse::<i128>() {
+            let bound = |outcome| self.attempts
+                .iter()
+                .filter(move |attempt| attempt.outcome == outcome)
+                .filter_map(|attempt| attempt.answer.parse::<i128>().ok());
+            if let Some(too_high) = bound(Outcome::TooHigh).min() {
+                if number >= too_high {
+                    return Err(format!("answer {} is not lower than {}, which was too high", number, too_high));
+                }
+            }
+            if let Some(too_low) = bound(Outcome::TooLow).max() {
+                if number <= too_low {
+                    return Err(format!("answer {} is not higher than {}, which was too low", number, too_low));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn append(&mut self, answer: &str, outcome: Outcome) -> io::Result<()> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let attempt = Attempt { timestamp, outcome, answer: answer.to_string() };
+        if let Some(dir) = self.file_name.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.file_name)?;
+        writeln!(file, "{}\t{}\t{}", attempt.timestamp, attempt.outcome.to_log_str(), attempt.answer)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_attempt_log_check() {
+    let dir = crate::testing::temp_dir("attempt-log-check");
+    let mut log = AttemptLog::load(dir.join("log")).unwrap();
+    assert_eq!(log.check("100"), Ok(()));
+
+    log.append("100", Outcome::TooHigh).unwrap();
+    log.append("20", Outcome::TooLow).unwrap();
+    log.append("50", Outcome::Wrong).unwrap();
+    log.append("60", Outcome::RateLimited { wait: None }).unwrap();
+    assert!(log.check("100").is_err());
+    assert!(log.check("101").is_err());
+    assert!(log.check("20").is_err());
+    assert!(log.check("19").is_err());
+    assert!(log.check("50").is_err());
+    assert_eq!(log.check("60"), Ok(()));
+    assert_eq!(log.check("99"), Ok(()));
+    assert_eq!(log.check("abc"), Ok(()));
+
+    let reloaded = AttemptLog::load(dir.join("log")).unwrap();
+    assert_eq!(reloaded.attempts, log.attempts);
+
+    log.append("60", Outcome::Correct).unwrap();
+    assert!(log.check("61").is_err());
+}
+
+/// Submits an answer, unless the attempt log shows that it is already known to be wrong. Every
+/// submission is appended to the log.
+pub fn submit(
+    base_url: &str, session_cookie: &str, year: u32, day: u32, part: u32, answer: &str,
+    log: &mut AttemptLog) -> Result<Outcome, Box<dyn Error>>
+{
+    log.check(answer)?;
+    let url = format!("{}/{}/day/{}/answer", base_url, year, day);
+    let html = reqwest::Client::new()
+        .post(&url)
+        .header(reqwest::header::COOKIE, format!("session={}", session_cookie))
+        .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+        .send()?
+        .error_for_status()?
+        .text()?;
+    let outcome = classify(&html);
+    log.append(answer, outcome.clone())?;
+    Ok(outcome)
+}
+
+#[test]
+fn test_submit() {
+    let server = crate::testing::MockServer::start(|request| {
+        let html = if request.body.contains("answer=42") {
+            "<article><p>That's not the right answer; your answer is too high.</p></article>"
+        } else {
+            "<article><p>That's the right answer!</p></article>"
+        };
+        (200, html.to_string())
+    });
+    let dir = crate::testing::temp_dir("submit");
+    let mut log = AttemptLog::load(dir.join("log")).unwrap();
+
+    let outcome = submit(&server.base_url, "cookie", 2020, 7, 2, "42", &mut log).unwrap();
+    assert_eq!(outcome, Outcome::TooHigh);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2020/day/7/answer");
+    assert_eq!(requests[0].header("Cookie"), Some("session=cookie"));
+    assert_eq!(requests[0].body, "level=2&answer=42");
+
+    assert!(submit(&server.base_url, "cookie", 2020, 7, 2, "43", &mut log).is_err());
+    assert_eq!(server.requests().len(), 1);
+
+    let outcome = submit(&server.base_url, "cookie", 2020, 7, 2, "41", &mut log).unwrap();
+    assert_eq!(outcome, Outcome::Correct);
+    assert_eq!(log.attempts.len(), 2);
+}
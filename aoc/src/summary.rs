@@ -0,0 +1,331 @@
+//! Runs many days at once on a pool of threads, and summarizes the results in a table.
+
+use crate::answers::Verdict;
+use crate::{answers, ParseFn, Phase};
+use serde_json::{json, Value};
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Days that take longer than this in total are marked as slow.
+pub const SLOW_DAY: Duration = Duration::from_secs(1);
+
+/// The result of running one phase of a day.
+#[derive(Debug, PartialEq)]
+pub struct PhaseOutcome {
+    pub phase: Phase,
+    pub duration: Duration,
+    /// The answer (empty for parsing), or the error or panic message.
+    pub result: Result<String, String>,
+    /// Whether the answer matches the recorded one. Only set for parts that succeeded.
+    pub verdict: Option<Verdict>,
+}
+
+impl PhaseOutcome {
+    fn status(&self) -> String {
+        match (&self.result, &self.verdict) {
+            (Err(_), _) => "ERROR".to_string(),
+            (Ok(_), Some(verdict)) => verdict.to_string(),
+            (Ok(_), None) => String::new(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DayOutcome {
+    pub day: u32,
+    /// Parsing, followed by each part that was run. Parts are not run if parsing failed.
+    pub phases: Vec<PhaseOutcome>,
+}
+
+impl DayOutcome {
+    pub fn duration(&self) -> Duration {
+        self.phases.iter().map(|phase| phase.duration).sum()
+    }
+
+    pub fn is_slow(&self) -> bool {
+        self.duration() > SLOW_DAY
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => format!("panicked: {}", message),
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => format!("panicked: {}", message),
+            Err(_) => "panicked".to_string(),
+        },
+    }
+}
+
+/// Runs `func`, turning a panic into an error, and measures how long it took.
+fn timed<F, R>(func: F) -> (Result<R, String>, Duration)
+    where F: FnOnce() -> Result<R, String>
+{
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(func)).unwrap_or_else(|payload| Err(panic_message(payload)));
+    (result, start.elapsed())
+}
+
+const WORKER_THREAD_NAME: &str = "summary-worker";
+
+/// Keeps the panic hook from printing the panics of worker threads, which `run_day` already
+/// reports as errors. Panics on other threads are printed as usual.
+fn silence_worker_panics() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() != Some(WORKER_THREAD_NAME) {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+fn run_day(day: u32, parse: ParseFn, input: &str, parts: &[u32]) -> DayOutcome {
+    let (parsed, duration) = timed(|| parse(input).map_err(|err| err.to_string()));
+    let mut phases = vec![PhaseOutcome {
+        phase: Phase::Parse,
+        duration,
+        result: parsed.as_ref().map(|_| String::new()).map_err(String::clone),
+        verdict: None,
+    }];
+    if let Ok(parsed) = parsed {
+        for &part in parts {
+            let (result, duration) = timed(|| parsed.run(part).map_err(|err| err.to_string()));
+            phases.push(PhaseOutcome { phase: Phase::Part(part), duration, result, verdict: None });
+        }
+    }
+    DayOutcome { day, phases }
+}
+
+/// Runs the given parts of each day on `threads` threads, and returns the outcomes sorted by day,
+/// with the answers verified against the recorded ones for `year`. Each job is a day, its parse
+/// function and its input.
+pub(crate) fn run_parallel(year: u32, jobs: Vec<(u32, ParseFn, String)>, parts: &[u32], threads: usize) -> Vec<DayOutcome> {
+    let jobs = Arc::new(jobs);
+    let parts = Arc::new(parts.to_vec());
+    let next_job = Arc::new(AtomicUsize::new(0));
+    let (sender, receiver) = mpsc::channel();
+    silence_worker_panics();
+    let workers = (0..threads.max(1).min(jobs.len()))
+        .map(|_| {
+            let (jobs, parts, next_job, sender) = (jobs.clone(), parts.clone(), next_job.clone(), sender.clone());
+            thread::Builder::new().name(WORKER_THREAD_NAME.to_string()).spawn(move || {
+                while let Some((day, parse, input)) = jobs.get(next_job.fetch_add(1, Ordering::SeqCst)) {
+                    sender.send(run_day(*day, *parse, input, &parts)).unwrap();
+                }
+            }).unwrap()
+        })
+        .collect::<Vec<_>>();
+    drop(sender);
+    let mut outcomes = receiver.iter().collect::<Vec<_>>();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    outcomes.sort_by_key(|outcome| outcome.day);
+    for outcome in &mut outcomes {
+        for phase in &mut outcome.phases {
+            if let (Phase::Part(part), Ok(answer)) = (phase.phase, &phase.result) {
+                phase.verdict = Some(answers::verify(year, outcome.day, part, answer));
+            }
+        }
+    }
+    outcomes
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{}.{:03} s", duration.as_secs(), duration.subsec_millis())
+}
+
+/// Formats the outcomes as a table with a row per phase, followed by the wall time.
+pub fn table(outcomes: &[DayOutcome], wall_time: Duration) -> String {
+    let header = ["Day", "Part", "Time", "Answer", "Status"];
+    let mut rows = Vec::new();
+    for outcome in outcomes {
+        let day = format!("{}{}", outcome.day, if outcome.is_slow() { "*" } else { "" });
+        for phase in &outcome.phases {
+            let part = match phase.phase {
+                Phase::Parse => "parse".to_string(),
+                Phase::Part(part) => part.to_string(),
+            };
+            let answer = match &phase.result {
+                Ok(answer) => answer.clone(),
+                Err(err) => err.clone(),
+            };
+            rows.push([day.clone(), part, format_duration(phase.duration), answer, phase.status()]);
+        }
+    }
+
+    let mut widths = header.iter().map(|cell| cell.len()).collect::<Vec<_>>();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let format_row = |cells: &[&str]| {
+        let line = cells.iter().zip(&widths).enumerate()
+            .map(|(i, (cell, &width))| if i < 3 { format!("{:>w$}", cell, w = width) } else { format!("{:w$}", cell, w = width) })
+            .collect::<Vec<_>>()
+            .join("  ");
+        line.trim_end().to_string() + "\n"
+    };
+
+    let mut out = format_row(&header);
+    out += &format_row(&widths.iter().map(|&width| "-".repeat(width)).collect::<Vec<_>>().iter().map(String::as_str).collect::<Vec<_>>());
+    for row in &rows {
+        out += &format_row(&row.iter().map(String::as_str).collect::<Vec<_>>());
+    }
+    let total = outcomes.iter().map(DayOutcome::duration).sum();
+    out += &format!("Total wall time: {} (sum of all phases: {})\n", format_duration(wall_time), format_duration(total));
+    if outcomes.iter().any(DayOutcome::is_slow) {
+        out += &format!("* took more than {} in total\n", format_duration(SLOW_DAY));
+    }
+    out
+}
+
+/// Formats the outcomes as JSON, for consumption by other tools.
+pub fn to_json(year: u32, outcomes: &[DayOutcome], wall_time: Duration) -> Value {
+    let days = outcomes.iter()
+        .map(|outcome| {
+            let phases = outcome.phases.iter()
+                .map(|phase| {
+                    let (answer, error) = match &phase.result {
+                        Ok(answer) => (Some(answer), None),
+                        Err(err) => (None, Some(err)),
+                    };
+                    json!({
+                        "phase": match phase.phase {
+                            Phase::Parse => json!("parse"),
+                            Phase::Part(part) => json!(part),
+                        },
+                        "time_secs": phase.duration.as_secs_f64(),
+                        "answer": if phase.phase == Phase::Parse { None } else { answer },
+                        "error": error,
+                        "status": match (&phase.result, &phase.verdict) {
+                            (Err(_), _) => Some("error"),
+                            (Ok(_), Some(Verdict::Ok)) => Some("ok"),
+                            (Ok(_), Some(Verdict::Mismatch { .. })) => Some("mismatch"),
+                            (Ok(_), Some(Verdict::Unknown)) => Some("unknown"),
+                            (Ok(_), None) => None,
+                        },
+                        "expected": match &phase.verdict {
+                            Some(Verdict::Mismatch { expected }) => Some(expected),
+                            _ => None,
+                        },
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "day": outcome.day,
+                "time_secs": outcome.duration().as_secs_f64(),
+                "slow": outcome.is_slow(),
+                "phases": phases,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "year": year,
+        "wall_time_secs": wall_time.as_secs_f64(),
+        "days": days,
+    })
+}
+
+#[cfg(test)]
+fn example_outcomes() -> Vec<DayOutcome> {
+    let phase = |phase, millis, result: Result<&str, &str>, verdict| PhaseOutcome {
+        phase,
+        duration: Duration::from_millis(millis),
+        result: result.map(str::to_string).map_err(str::to_string),
+        verdict,
+    };
+    vec![
+        DayOutcome { day: 1, phases: vec![
+            phase(Phase::Parse, 1, Ok(""), None),
+            phase(Phase::Part(1), 2, Ok("514579"), Some(Verdict::Ok)),
+            phase(Phase::Part(2), 3, Ok("241861950"), Some(Verdict::Mismatch { expected: "42".to_string() })),
+        ]},
+        DayOutcome { day: 15, phases: vec![
+            phase(Phase::Parse, 0, Ok(""), None),
+            phase(Phase::Part(1), 0, Ok("436"), Some(Verdict::Unknown)),
+            phase(Phase::Part(2), 1500, Err("panicked: oops"), None),
+        ]},
+        DayOutcome { day: 16, phases: vec![
+            phase(Phase::Parse, 0, Err("invalid input at line 1, column 1: expected a rule"), None),
+        ]},
+    ]
+}
+
+#[test]
+fn test_table() {
+    assert_eq!(table(&example_outcomes(), Duration::from_millis(1504)), "\
+Day   Part     Time  Answer                                              Status
+---  -----  -------  --------------------------------------------------  ---------------------
+  1  parse  0.001 s
+  1      1  0.002 s  514579                                              OK
+  1      2  0.003 s  241861950                                           MISMATCH, expected 42
+15*  parse  0.000 s
+15*      1  0.000 s  436                                                 UNKNOWN
+15*      2  1.500 s  panicked: oops                                      ERROR
+ 16  parse  0.000 s  invalid input at line 1, column 1: expected a rule  ERROR
+Total wall time: 1.504 s (sum of all phases: 1.506 s)
+* took more than 1.000 s in total
+");
+}
+
+#[test]
+fn test_to_json() {
+    let json = to_json(2020, &example_outcomes(), Duration::from_millis(1504));
+    assert_eq!(json["year"], 2020);
+    assert_eq!(json["days"][0]["phases"][0], json!({
+        "phase": "parse", "time_secs": 0.001, "answer": null, "error": null, "status": null, "expected": null,
+    }));
+    assert_eq!(json["days"][0]["phases"][2]["status"], "mismatch");
+    assert_eq!(json["days"][0]["phases"][2]["expected"], "42");
+    assert_eq!(json["days"][1]["slow"], true);
+    assert_eq!(json["days"][1]["phases"][2]["error"], "panicked: oops");
+    assert_eq!(json["days"][2]["phases"].as_array().unwrap().len(), 1);
+}
+
+#[cfg(test)]
+struct PanickingDay;
+
+#[cfg(test)]
+impl crate::Solution for PanickingDay {
+    const YEAR: u32 = 2020;
+    const DAY: u32 = 3;
+
+    type Input = u32;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<u32, crate::ParseError> {
+        crate::parse::value(input.trim())
+    }
+
+    fn part1(input: &u32) -> u32 {
+        input * 2
+    }
+
+    fn part2(_input: &u32) -> u32 {
+        panic!("oops")
+    }
+}
+
+#[test]
+fn test_run_parallel() {
+    let parse = crate::parse_input::<PanickingDay> as ParseFn;
+    let jobs = (1..=10).map(|day| (day, parse, if day == 7 { "x".to_string() } else { day.to_string() })).collect();
+    let outcomes = run_parallel(1, jobs, &[1, 2], 3);
+    assert_eq!(outcomes.iter().map(|outcome| outcome.day).collect::<Vec<_>>(), (1..=10).collect::<Vec<_>>());
+    let results = |day: usize| outcomes[day - 1].phases.iter().map(|phase| phase.result.clone()).collect::<Vec<_>>();
+    assert_eq!(results(4), vec![Ok(String::new()), Ok("8".to_string()), Err("panicked: oops".to_string())]);
+    assert_eq!(results(7).len(), 1);
+    assert!(results(7)[0].is_err());
+    assert_eq!(outcomes[0].phases[1].verdict, Some(Verdict::Unknown));
+}
@@ -0,0 +1,216 @@
+//! Helpers for tests that need a filesystem or an HTTP server.
+
+use crate::unlock::Clock;
+use std::cell::Cell;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{env, fs, process};
+
+/// A directory that is removed with everything in it when dropped.
+pub struct TempDir(PathBuf);
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Returns a fresh, empty directory that is unique to this process and name.
+pub fn temp_dir(name: &str) -> TempDir {
+    let dir = env::temp_dir().join(format!("aoc-test-{}-{}", process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    TempDir(dir)
+}
+
+#[test]
+fn test_temp_dir() {
+    let dir = temp_dir("temp-dir");
+    fs::write(dir.join("file"), "contents").unwrap();
+    let path = dir.to_path_buf();
+    drop(dir);
+    assert!(!path.exists());
+}
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A minimal HTTP server on a local port that answers each request with the status and body
+/// returned by a handler function, and keeps track of the requests it received.
+pub struct MockServer {
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    pub fn start<F>(handler: F) -> MockServer
+        where F: Fn(&Request) -> (u16, String) + Send + 'static
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let server_requests = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let request = read_request(&stream);
+                let (status, body) = handler(&request);
+                server_requests.lock().unwrap().push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status, body.len(), body).unwrap();
+            }
+        });
+        MockServer { base_url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Request {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap().to_string();
+    let path = parts.next().unwrap().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let colon = line.find(':').unwrap();
+        headers.push((line[..colon].to_string(), line[colon + 1..].trim().to_string()));
+    }
+
+    let mut request = Request { method, path, headers, body: String::new() };
+    let content_length = request.header("Content-Length").map_or(0, |len| len.parse().unwrap());
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    request.body = String::from_utf8(body).unwrap();
+    request
+}
+
+pub const MOCK_SESSION_COOKIE: &str = "0123456789abcdef";
+pub const MOCK_INPUT: &str = "1721\n979\n366\n299\n675\n1456\n";
+pub const MOCK_PUZZLE_HTML: &str = "\
+<!DOCTYPE html>
+<html lang=\"en-us\"><head><title>Day 1 - Advent of Code 2020</title></head><body>
+<main>
+<article class=\"day-desc\"><h2>--- Day 1: Report Repair ---</h2>
+<p>For example, suppose your expense report contained the following:</p>
+<pre><code>1721
+979
+<em>366</em>
+</code></pre>
+<p>In this list, the two entries that sum to <code>2020</code> are <code>1721</code> and <code>299</code>. \
+Multiplying them together produces <code>1721 * 299 = 514579</code>, so the correct answer is <code><em>514579</em></code>.</p>
+<pre><code>a &lt; b &amp;&amp; c
+</code></pre>
+</article>
+</main>
+</body></html>
+";
+
+pub const MOCK_NOT_UNLOCKED: &str = "Please don't repeatedly request this endpoint before it unlocks! \
+The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant \
+this puzzle becomes available.\n";
+pub const MOCK_LOGIN_HTML: &str = "\
+<!DOCTYPE html>
+<html lang=\"en-us\"><head><title>Advent of Code 2020</title></head><body>
+<main>
+<p>To play, please identify yourself via one of these services:</p>
+<p><a href=\"/auth/github\">[GitHub]</a> <a href=\"/auth/google\">[Google]</a></p>
+</main>
+</body></html>
+";
+
+pub const MOCK_SETTINGS_HTML: &str = "\
+<!DOCTYPE html>
+<html lang=\"en-us\"><head><title>Settings - Advent of Code 2020</title></head><body>
+<header><div class=\"user\">Mock User</div><nav><a href=\"/auth/logout\">[Log Out]</a></nav></header>
+<main><form method=\"post\" action=\"/settings\"></form></main>
+</body></html>
+";
+
+/// Starts a server that mimics the Advent of Code website for 2020 day 1: it serves `MOCK_INPUT`
+/// and `MOCK_PUZZLE_HTML`, responds with 400 like the real site if the session cookie is not
+/// `MOCK_SESSION_COOKIE` (or serves a login page instead of the settings), with 404 for days that
+/// don't exist, and with 500 for day 13. For day 3, it responds with 200 but serves the "not
+/// unlocked yet" message and a login page instead.
+pub fn mock_aoc_server() -> MockServer {
+    MockServer::start(|request| {
+        let logged_in = request.header("Cookie") == Some(&format!("session={}", MOCK_SESSION_COOKIE));
+        match request.path.as_str() {
+            "/settings" if logged_in => (200, MOCK_SETTINGS_HTML.to_string()),
+            "/settings" => (200, MOCK_LOGIN_HTML.to_string()),
+            "/2020/day/13" | "/2020/day/13/input" => (500, "Internal Server Error".to_string()),
+            "/2020/day/1/input" if !logged_in =>
+                (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_string()),
+            "/2020/day/1/input" => (200, MOCK_INPUT.to_string()),
+            "/2020/day/1" => (200, MOCK_PUZZLE_HTML.to_string()),
+            "/2020/day/3/input" => (200, MOCK_NOT_UNLOCKED.to_string()),
+            "/2020/day/3" => (200, MOCK_LOGIN_HTML.to_string()),
+            _ => (404, "404 Not Found".to_string()),
+        }
+    })
+}
+
+/// A clock that only advances when slept on. The time can be shared with other code through
+/// `now`.
+pub struct FakeClock {
+    pub now: Rc<Cell<SystemTime>>,
+    pub sleeps: Vec<Duration>,
+}
+
+impl FakeClock {
+    pub fn new(now: SystemTime) -> FakeClock {
+        FakeClock { now: Rc::new(Cell::new(now)), sleeps: Vec::new() }
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        self.now.get()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+        self.sleeps.push(duration);
+    }
+}
@@ -0,0 +1,176 @@
+//! Waiting for a puzzle to unlock, and fetching it as soon as it does.
+
+use std::cmp;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Source of the current time, which can be replaced in tests.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&mut self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Puzzles unlock at midnight US Eastern Standard Time (UTC-5) on the given day of December.
+pub fn unlock_time(year: u32, day: u32) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    UNIX_EPOCH + Duration::from_secs((days * 86400 + 5 * 3600) as u64)
+}
+
+/// Number of days since 1970-01-01 of the given date in the proleptic Gregorian calendar.
+/// See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[test]
+fn test_unlock_time() {
+    assert_eq!(unlock_time(2020, 1), UNIX_EPOCH + Duration::from_secs(1606798800));
+    assert_eq!(unlock_time(2020, 25), UNIX_EPOCH + Duration::from_secs(1608872400));
+    assert_eq!(unlock_time(2015, 1), UNIX_EPOCH + Duration::from_secs(1448946000));
+}
+
+/// How long after unlocking a puzzle still counts as the next one, so that starting `wait` just
+/// too late still fetches it.
+const NEXT_DAY_GRACE: Duration = Duration::from_secs(5 * 60);
+
+/// Returns the first day of the year whose puzzle has not unlocked yet, or unlocked less than
+/// `NEXT_DAY_GRACE` ago.
+pub fn next_day(clock: &dyn Clock, year: u32) -> Option<u32> {
+    let now = clock.now();
+    (1..=25).find(|&day| unlock_time(year, day) + NEXT_DAY_GRACE > now)
+}
+
+/// Sleeps until the given time, printing a countdown in the meantime.
+pub fn wait_until(clock: &mut dyn Clock, time: SystemTime) {
+    while let Ok(remaining) = time.duration_since(clock.now()) {
+        if remaining == Duration::from_secs(0) {
+            break;
+        }
+        print!("\rUnlocks in {}   ", format_countdown(remaining));
+        io::stdout().flush().unwrap();
+        let tick = if remaining > Duration::from_secs(600) { Duration::from_secs(60) } else { Duration::from_secs(1) };
+        clock.sleep(cmp::min(remaining, tick));
+    }
+    println!("\rUnlocked!{}", " ".repeat(20));
+}
+
+fn format_countdown(duration: Duration) -> String {
+    let secs = (duration.as_millis() + 999) as u64 / 1000;
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[test]
+fn test_format_countdown() {
+    assert_eq!(format_countdown(Duration::from_millis(3_723_001)), "1:02:04");
+    assert_eq!(format_countdown(Duration::from_secs(59)), "0:00:59");
+}
+
+/// An error that may go away by itself, so that `retry_with_backoff` tries again: the puzzle has
+/// not been unlocked yet, or the website is having trouble.
+#[derive(Debug)]
+pub struct TransientError(pub String);
+
+impl Display for TransientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for TransientError {}
+
+pub const MAX_ATTEMPTS: u32 = 8;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Calls `fetch` until it succeeds, doubling the delay between attempts each time, up to
+/// `MAX_ATTEMPTS` attempts. Returns the last error if all of them fail, and any error other than a
+/// `TransientError` right away.
+pub fn retry_with_backoff<T, F>(clock: &mut dyn Clock, mut fetch: F) -> Result<T, Box<dyn Error>>
+    where F: FnMut() -> Result<T, Box<dyn Error>>
+{
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match fetch() {
+            Ok(result) => return Ok(result),
+            Err(err) if attempt >= MAX_ATTEMPTS || !err.is::<TransientError>() => return Err(err),
+            Err(err) => {
+                println!("Attempt {} failed, retrying in {} s: {}", attempt, backoff.as_secs(), err);
+                clock.sleep(backoff);
+                backoff = cmp::min(backoff * 2, MAX_BACKOFF);
+                attempt += 1;
+            },
+        }
+    }
+}
+
+#[test]
+fn test_wait_until() {
+    let unlock = unlock_time(2020, 7);
+    let mut clock = crate::testing::FakeClock::new(unlock - Duration::from_millis(900_500));
+    wait_until(&mut clock, unlock);
+    assert_eq!(clock.now(), unlock);
+    assert_eq!(clock.sleeps.iter().filter(|&&sleep| sleep == Duration::from_secs(60)).count(), 6);
+    assert_eq!(clock.sleeps.iter().filter(|&&sleep| sleep == Duration::from_secs(1)).count(), 540);
+    assert_eq!(*clock.sleeps.last().unwrap(), Duration::from_millis(500));
+
+    let sleeps = clock.sleeps.len();
+    wait_until(&mut clock, unlock - Duration::from_secs(1));
+    assert_eq!(clock.sleeps.len(), sleeps);
+}
+
+#[test]
+fn test_next_day() {
+    let clock = crate::testing::FakeClock::new(unlock_time(2020, 7) - Duration::from_secs(1));
+    assert_eq!(next_day(&clock, 2020), Some(7));
+    clock.now.set(unlock_time(2020, 8));
+    assert_eq!(next_day(&clock, 2020), Some(8));
+    clock.now.set(unlock_time(2020, 8) + Duration::from_secs(600));
+    assert_eq!(next_day(&clock, 2020), Some(9));
+    clock.now.set(unlock_time(2020, 1) - Duration::from_secs(86400 * 30));
+    assert_eq!(next_day(&clock, 2020), Some(1));
+    assert_eq!(next_day(&clock, 2019), None);
+}
+
+#[test]
+fn test_retry_with_backoff() {
+    let unlock = unlock_time(2020, 7);
+    let mut clock = crate::testing::FakeClock::new(unlock - Duration::from_secs(2));
+    let now = clock.now.clone();
+    let result = retry_with_backoff(&mut clock, || {
+        if now.get() < unlock { Err(TransientError("not unlocked yet".to_string()).into()) } else { Ok("input") }
+    });
+    assert_eq!(result.unwrap(), "input");
+    assert_eq!(clock.sleeps, vec![Duration::from_secs(1), Duration::from_secs(2)]);
+
+    let mut clock = crate::testing::FakeClock::new(unlock);
+    let result: Result<(), _> = retry_with_backoff(&mut clock, || Err(TransientError("server down".to_string()).into()));
+    assert_eq!(result.unwrap_err().to_string(), "server down");
+    assert_eq!(clock.sleeps.len() as u32, MAX_ATTEMPTS - 1);
+    assert_eq!(*clock.sleeps.last().unwrap(), MAX_BACKOFF);
+
+    let mut clock = crate::testing::FakeClock::new(unlock);
+    let result: Result<(), _> = retry_with_backoff(&mut clock, || Err("invalid session cookie".into()));
+    assert_eq!(result.unwrap_err().to_string(), "invalid session cookie");
+    assert!(clock.sleeps.is_empty());
+}
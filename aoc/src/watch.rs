@@ -0,0 +1,275 @@
+//! Watch mode: reruns a day whenever its source, input or examples change.
+//!
+//! The runner that is watching was compiled before the change, so it can't run the new code itself.
+//! Instead, each run goes through cargo: `summary --json` for the answers and timings of the
+//! parts, and `cargo test` for the tests of the day, which include its examples.
+
+use crate::answers::Verdict;
+use crate::bench::format_nanos;
+use crate::{example_file_name, input_file_name, Phase};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+/// How often the watched files are checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The files that a day's answers and tests depend on: its source, its input and its examples.
+pub fn watched_files(year: u32, day: u32) -> io::Result<Vec<String>> {
+    let mut file_names = vec![format!("src/day{:02}.rs", day), input_file_name(year, day)];
+    let example_0 = example_file_name(year, day, 0);
+    let examples_dir = Path::new(&example_0).parent().unwrap();
+    if examples_dir.is_dir() {
+        let prefix = format!("{:02}-", day);
+        for entry in fs::read_dir(examples_dir)? {
+            let file_name = entry?.file_name().to_string_lossy().into_owned();
+            if file_name.starts_with(&prefix) && file_name.ends_with(".example") {
+                file_names.push(examples_dir.join(file_name).to_string_lossy().into_owned());
+            }
+        }
+    }
+    file_names.sort();
+    Ok(file_names)
+}
+
+/// The modification time of each file, or `None` if it doesn't exist.
+pub type Snapshot = BTreeMap<String, Option<SystemTime>>;
+
+pub fn snapshot(file_names: &[String]) -> Snapshot {
+    file_names.iter()
+        .map(|file_name| (file_name.clone(), fs::metadata(file_name).and_then(|metadata| metadata.modified()).ok()))
+        .collect()
+}
+
+/// The files that were created, deleted or modified between two snapshots.
+pub fn changed_files(old: &Snapshot, new: &Snapshot) -> Vec<String> {
+    old.keys().chain(new.keys())
+        .filter(|file_name| old.get(*file_name).copied().flatten() != new.get(*file_name).copied().flatten())
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[test]
+fn test_changed_files() {
+    let time = |secs| Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+    let old = vec![("a".to_string(), time(1)), ("b".to_string(), time(2)), ("c".to_string(), None)].into_iter().collect();
+    let new = vec![("a".to_string(), time(1)), ("b".to_string(), time(3)), ("d".to_string(), time(4))].into_iter().collect();
+    assert_eq!(changed_files(&old, &new), vec!["b", "d"]);
+    assert!(changed_files(&new, &new).is_empty());
+}
+
+/// The outcome of one phase of a run, as reported by `summary --json`.
+#[derive(Debug, PartialEq)]
+pub struct PhaseResult {
+    pub phase: Phase,
+    pub duration: Duration,
+    pub result: Result<String, String>,
+    pub verdict: Option<Verdict>,
+}
+
+impl PhaseResult {
+    /// The answer and its verdict, or the error.
+    fn describe(&self) -> String {
+        match (&self.result, &self.verdict) {
+            (Ok(_), _) if self.phase == Phase::Parse => "ok".to_string(),
+            (Ok(answer), Some(verdict)) => format!("{} [{}]", answer, verdict),
+            (Ok(answer), None) => answer.clone(),
+            (Err(err), _) => format!("error: {}", err),
+        }
+    }
+}
+
+/// Parses the phases of the single day in the output of `summary --json`.
+pub fn parse_summary(json: &str) -> Result<Vec<PhaseResult>, Box<dyn Error>> {
+    let summary = serde_json::from_str::<Value>(json)?;
+    let phases = summary["days"][0]["phases"].as_array().ok_or("no phases in summary")?;
+    phases.iter()
+        .map(|phase| {
+            let str_field = |name: &str| phase[name].as_str().map(str::to_string);
+            let result = match str_field("error") {
+                Some(err) => Err(err),
+                None => Ok(str_field("answer").unwrap_or_default()),
+            };
+            Ok(PhaseResult {
+                phase: match &phase["phase"] {
+                    Value::String(phase) if phase == "parse" => Phase::Parse,
+                    part => Phase::Part(part.as_u64().ok_or("invalid phase in summary")? as u32),
+                },
+                duration: Duration::from_secs_f64(phase["time_secs"].as_f64().ok_or("no time in summary")?),
+                result,
+                verdict: match phase["status"].as_str() {
+                    Some("ok") => Some(Verdict::Ok),
+                    Some("mismatch") => Some(Verdict::Mismatch { expected: str_field("expected").unwrap_or_default() }),
+                    Some("unknown") => Some(Verdict::Unknown),
+                    _ => None,
+                },
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_summary() {
+    let json = r#"{"year": 2020, "days": [{"day": 7, "phases": [
+        {"phase": "parse", "time_secs": 0.5, "answer": null, "error": null, "status": null, "expected": null},
+        {"phase": 1, "time_secs": 0.25, "answer": "42", "error": null, "status": "mismatch", "expected": "43"},
+        {"phase": 2, "time_secs": 0.0, "answer": null, "error": "oops", "status": "error", "expected": null}
+    ]}]}"#;
+    assert_eq!(parse_summary(json).unwrap(), vec![
+        PhaseResult { phase: Phase::Parse, duration: Duration::from_millis(500), result: Ok(String::new()), verdict: None },
+        PhaseResult {
+            phase: Phase::Part(1), duration: Duration::from_millis(250), result: Ok("42".to_string()),
+            verdict: Some(Verdict::Mismatch { expected: "43".to_string() }),
+        },
+        PhaseResult { phase: Phase::Part(2), duration: Duration::from_secs(0), result: Err("oops".to_string()), verdict: None },
+    ]);
+    assert!(parse_summary("error[E0308]: mismatched types").is_err());
+}
+
+/// Formats the results with one line per phase. Answers that changed since the previous run are
+/// shown as `old -> new`, and each time is compared to the previous one.
+pub fn compare(previous: &[PhaseResult], current: &[PhaseResult]) -> String {
+    current.iter()
+        .map(|result| {
+            let prev = previous.iter().find(|prev| prev.phase == result.phase);
+            let description = match prev {
+                Some(prev) if prev.describe() != result.describe() => format!("{} -> {}", prev.describe(), result.describe()),
+                _ => result.describe(),
+            };
+            let nanos = result.duration.as_nanos() as f64;
+            let time = match prev {
+                Some(prev) if prev.duration.as_nanos() > 0 => {
+                    let prev_nanos = prev.duration.as_nanos() as f64;
+                    format!("{} (was {}, {:+.1}%)", format_nanos(nanos), format_nanos(prev_nanos), 100.0 * (nanos - prev_nanos) / prev_nanos)
+                },
+                _ => format_nanos(nanos),
+            };
+            format!("{:<6}  {}  {}\n", result.phase.to_string(), description, time)
+        })
+        .collect()
+}
+
+#[test]
+fn test_compare() {
+    let result = |phase, millis, answer: &str| PhaseResult {
+        phase,
+        duration: Duration::from_millis(millis),
+        result: Ok(answer.to_string()),
+        verdict: if phase == Phase::Parse { None } else { Some(Verdict::Unknown) },
+    };
+    let previous = vec![result(Phase::Parse, 2, ""), result(Phase::Part(1), 10, "42")];
+    let current = vec![result(Phase::Parse, 1, ""), result(Phase::Part(1), 10, "43"), result(Phase::Part(2), 5, "7")];
+    assert_eq!(compare(&previous, &current), "\
+parse   ok  1.000000 ms (was 2.000000 ms, -50.0%)
+part 1  42 [UNKNOWN] -> 43 [UNKNOWN]  10.000000 ms (was 10.000000 ms, +0.0%)
+part 2  7 [UNKNOWN]  5.000000 ms
+");
+    assert_eq!(compare(&[], &previous[..1]), "parse   ok  2.000000 ms\n");
+}
+
+fn cargo() -> Command {
+    // Set when the runner itself was started by `cargo run`, to the same cargo.
+    Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+}
+
+/// Builds the runner and runs the given parts of the day on the real input. On failure, returns
+/// the output of cargo, such as compiler errors.
+pub fn run_parts(day: u32, parts: &[u32]) -> Result<Vec<PhaseResult>, String> {
+    let mut command = cargo();
+    command.args(&["run", "--release", "--quiet", "--", "summary", "--json", "--threads", "1"]);
+    if let [part] = parts {
+        command.args(&["--part", &part.to_string()]);
+    }
+    let output = command.arg(day.to_string()).output().map_err(|err| format!("could not run cargo: {}", err))?;
+    parse_summary(&String::from_utf8_lossy(&output.stdout))
+        .map_err(|_| String::from_utf8_lossy(&output.stderr).trim_end().to_string())
+}
+
+/// Summarizes the output of `cargo test` as the number of tests that passed, followed by the
+/// name and output of each test that failed. Returns `None` if no tests ran.
+pub fn summarize_tests(output: &str) -> Option<String> {
+    let test_re = Regex::new(r"^test (\S+) \.\.\. (\w+)$").unwrap();
+    let mut num_passed = 0;
+    let mut failed = Vec::new();
+    for line in output.lines() {
+        if let Some(caps) = test_re.captures(line) {
+            match &caps[2] {
+                "ok" => num_passed += 1,
+                "FAILED" => failed.push(caps[1].to_string()),
+                _ => {},
+            }
+        }
+    }
+    if num_passed == 0 && failed.is_empty() {
+        return None;
+    }
+    let mut summary = format!("tests: {} passed, {} failed\n", num_passed, failed.len());
+    for name in &failed {
+        let header = format!("---- {} stdout ----", name);
+        let details = output.lines()
+            .skip_while(|line| *line != header)
+            .skip(1)
+            .take_while(|line| !line.is_empty())
+            .map(|line| format!("    {}\n", line))
+            .collect::<String>();
+        summary += &format!("  {}\n{}", name, details);
+    }
+    Some(summary)
+}
+
+#[test]
+fn test_summarize_tests() {
+    let output = "\
+running 3 tests
+test day07::test_parse_error ... ok
+test day07::solution_tests::example_0 ... FAILED
+test day07::solution_tests::input ... ok
+
+failures:
+
+---- day07::solution_tests::example_0 stdout ----
+thread 'day07::solution_tests::example_0' panicked at '
+examples/2020/07-0.example, part 1: expected 4, got 5', src/day07.rs:80:1
+
+failures:
+    day07::solution_tests::example_0
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 80 filtered out
+";
+    assert_eq!(summarize_tests(output).unwrap(), "\
+tests: 2 passed, 1 failed
+  day07::solution_tests::example_0
+    thread 'day07::solution_tests::example_0' panicked at '
+    examples/2020/07-0.example, part 1: expected 4, got 5', src/day07.rs:80:1
+");
+    assert_eq!(summarize_tests("error[E0308]: mismatched types\n"), None);
+}
+
+/// Runs the tests of the day, and summarizes the results. If no tests ran, returns the output of
+/// cargo instead, such as compiler errors.
+pub fn run_tests(day: u32) -> String {
+    let output = cargo()
+        .args(&["test", "--release", "--lib", &format!("day{:02}::", day)])
+        // Backtraces would drown out the messages of the failed tests.
+        .env("RUST_BACKTRACE", "0")
+        .output();
+    let output = match output {
+        Ok(output) => output,
+        Err(err) => return format!("could not run cargo: {}\n", err),
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    summarize_tests(&stdout).unwrap_or_else(|| if output.status.success() {
+        "tests: none\n".to_string()
+    } else {
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    })
+}
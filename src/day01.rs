@@ -0,0 +1,64 @@
+use aoc::{parse, ParseError, Solution};
+use std::collections::HashSet;
+
+fn parse(input: &str) -> Result<HashSet<u64>, ParseError> {
+    parse::numbers(input)
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(parse("1721\n97x\n").unwrap_err(), ParseError::new(2, 1, "invalid value \"97x\": invalid digit found in string"));
+}
+
+fn part1(numbers: &HashSet<u64>) -> Result<u64, &'static str> {
+    for n in numbers.iter().filter(|&&n| n <= 2020) {
+        let m = 2020 - n;
+        if numbers.contains(&m) {
+            return Ok(n * m);
+        }
+    }
+    Err("no two entries sum to 2020")
+}
+
+fn part2(numbers: &HashSet<u64>) -> Result<u64, &'static str> {
+    for n in numbers {
+        for m in numbers {
+            if m + n <= 2020 {
+                let k = 2020 - m - n;
+                if numbers.contains(&k) {
+                    return Ok(n * m * k);
+                }
+            }
+        }
+    }
+    Err("no three entries sum to 2020")
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 1;
+
+    type Input = HashSet<u64>;
+    type Output1 = Result<u64, &'static str>;
+    type Output2 = Result<u64, &'static str>;
+
+    fn parse(input: &str) -> Result<HashSet<u64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &HashSet<u64>) -> Result<u64, &'static str> {
+        part1(input)
+    }
+
+    fn part2(input: &HashSet<u64>) -> Result<u64, &'static str> {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day01,
+    example_0 { 1, 2 },
+    input { 1 => 1005459, 2 => 92643264 },
+}
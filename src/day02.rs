@@ -0,0 +1,58 @@
+use aoc::{parse, ParseError, Solution};
+use regex::Regex;
+
+type Entry = (usize, usize, char, String);
+
+fn parse(input: &str) -> Result<Vec<Entry>, ParseError> {
+    let re = Regex::new(r"^(\d+)-(\d+) (.): (.*)$").unwrap();
+    parse::lines(input, |line| {
+        let caps = parse::captures(&re, line)?;
+        Ok((caps.get(1)?, caps.get(2)?, caps.get(3)?, caps.get(4)?))
+    })
+}
+
+fn part1(entries: &[Entry]) -> u64 {
+    entries.iter()
+        .filter(|&&(min, max, ch, ref pw)| {
+            let count = pw.chars().filter(|&c| c == ch).count();
+            min <= count && count <= max
+        })
+        .count() as u64
+}
+
+fn part2(entries: &[Entry]) -> u64 {
+    entries.iter()
+        .filter(|&&(min, max, ch, ref pw)| {
+            (pw.chars().nth(min - 1).unwrap() == ch) != (pw.chars().nth(max - 1).unwrap() == ch)
+        })
+        .count() as u64
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 2;
+
+    type Input = Vec<Entry>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Entry>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Entry>) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Entry>) -> u64 {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day02,
+    example_0 { 1, 2 },
+    input { 1 => 614, 2 => 354 },
+}
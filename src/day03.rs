@@ -0,0 +1,65 @@
+use aoc::{parse, Grid, ParseError, Solution};
+
+/// Whether each square has a tree.
+type Map = Grid<bool>;
+
+fn parse(input: &str) -> Result<Map, ParseError> {
+    parse::grid(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+fn count_trees(map: &Map, x_step: i64, y_step: i64) -> usize {
+    let mut x = x_step;
+    let mut y = y_step;
+    let mut count = 0;
+    while y < map.height() {
+        if *map.get_wrapping(x, y) {
+            count += 1;
+        }
+        x += x_step;
+        y += y_step;
+    }
+    count
+}
+
+fn part1(map: &Map) -> usize {
+    count_trees(map, 3, 1)
+}
+
+fn part2(map: &Map) -> usize {
+    [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)].iter()
+        .map(|&(x_step, y_step)| count_trees(map, x_step, y_step))
+        .product()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 3;
+
+    type Input = Map;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Map, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Map) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Map) -> usize {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day03,
+    example_0 { 1, 2 },
+    input { 1 => 191, 2 => 1478615040 },
+}
@@ -1,24 +1,19 @@
-#![feature(str_split_once)]
-#![feature(try_trait)]
-
+use aoc::{parse, ParseError, Solution};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
 use std::str::FromStr;
 
-fn parse(input: &str) -> Vec<HashMap<String, String>> {
-    input
-        .split("\n\n")
-        .map(|passport_lines| {
-            passport_lines
-                .split_whitespace()
-                .map(|field| {
-                    let (key, value) = field.split_once(':').unwrap();
-                    (key.to_string(), value.to_string())
-                })
-                .collect()
-        })
-        .collect()
+fn parse(input: &str) -> Result<Vec<HashMap<String, String>>, ParseError> {
+    parse::blocks(input, |passport_lines| {
+        passport_lines
+            .split_whitespace()
+            .map(|field| {
+                let (key, value) = parse::split_once(field, ":").map_err(|err| err.within(passport_lines, field))?;
+                Ok((key.to_string(), value.to_string()))
+            })
+            .collect()
+    })
 }
 
 fn is_complete(passport: &HashMap<String, String>) -> bool {
@@ -27,14 +22,8 @@ fn is_complete(passport: &HashMap<String, String>) -> bool {
         .all(|&key| passport.contains_key(key))
 }
 
-fn part1(input: &str) -> usize {
-    parse(input).into_iter().filter(is_complete).count()
-}
-
-#[test]
-fn test_part1() {
-    assert_eq!(part1(&aoc::example(0)), 2);
-    assert_eq!(part1(&aoc::input()), 210);
+fn part1(passports: &[HashMap<String, String>]) -> usize {
+    passports.iter().filter(|passport| is_complete(passport)).count()
 }
 
 struct ValidationError;
@@ -119,17 +108,37 @@ fn validate(passport: &HashMap<String, String>) -> ValidationResult {
     Ok(())
 }
 
-fn part2(input: &str) -> usize {
-    parse(input).iter().map(validate).filter(Result::is_ok).count()
+fn part2(passports: &[HashMap<String, String>]) -> usize {
+    passports.iter().map(validate).filter(Result::is_ok).count()
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(&aoc::example(2)), 0);
-    assert_eq!(part2(&aoc::example(3)), 4);
-    assert_eq!(part2(&aoc::input()), 131);
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 4;
+
+    type Input = Vec<HashMap<String, String>>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<HashMap<String, String>>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<HashMap<String, String>>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<HashMap<String, String>>) -> usize {
+        part2(input)
+    }
 }
 
-fn main() {
-    aoc::main(part1, part2);
+aoc::solution_tests! {
+    Day04,
+    example_0 { 1 },
+    example_2 { 2 },
+    example_3 { 2 },
+    input { 1 => 210, 2 => 131 },
 }
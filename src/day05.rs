@@ -0,0 +1,74 @@
+use aoc::{parse, ParseError, Solution};
+
+fn seat_id(pass: &str) -> Result<u64, ParseError> {
+    pass.char_indices().try_fold(0, |id, (i, c)| match c {
+        'F' | 'L' => Ok(id << 1),
+        'B' | 'R' => Ok(id << 1 | 1),
+        _ => Err(ParseError::at(pass, &pass[i..], format!("unexpected character {:?}", c))),
+    })
+}
+
+fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    parse::lines(input, seat_id)
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(parse("FBFBBFFRLR\nFBFBXFFRLR\n").unwrap_err(), ParseError::new(2, 5, "unexpected character 'X'"));
+}
+
+fn part1(seat_ids: &[u64]) -> u64 {
+    *seat_ids.iter().max().unwrap()
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&parse("FBFBBFFRLR").unwrap()), 357);
+    assert_eq!(part1(&parse("BFFFBBFRRR").unwrap()), 567);
+    assert_eq!(part1(&parse("FFFBBBFRRR").unwrap()), 119);
+    assert_eq!(part1(&parse("BBFFBBFRLL").unwrap()), 820);
+}
+
+fn part2(seat_ids: &[u64]) -> u64 {
+    let mut seat_ids = seat_ids.to_vec();
+    seat_ids.sort();
+    let mut gaps = seat_ids
+        .iter()
+        .zip(&seat_ids[1..])
+        .filter_map(|(a, b)| {
+            if b - a == 2 { Some(b - 1) } else { None }
+        })
+        .collect::<Vec<_>>()
+        .into_iter();
+    let my_seat = gaps.next().unwrap();
+    assert!(gaps.next().is_none());
+    my_seat
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 5;
+
+    type Input = Vec<u64>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<u64>) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<u64>) -> u64 {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day05,
+    input { 1 => 913, 2 => 717 },
+}
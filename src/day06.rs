@@ -0,0 +1,80 @@
+use aoc::{parse, ParseError, Solution};
+use std::collections::HashSet;
+
+type Group = Vec<HashSet<char>>;
+
+fn parse(input: &str) -> Result<Vec<Group>, ParseError> {
+    parse::blocks(input, |group| {
+        parse::lines(group, |person| {
+            person
+                .char_indices()
+                .map(|(i, c)| match c {
+                    'a'..='z' => Ok(c),
+                    _ => Err(ParseError::at(person, &person[i..], format!("unexpected character {:?}", c))),
+                })
+                .collect()
+        })
+    })
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(parse("abc\n\na\nb1\n").unwrap_err(), ParseError::new(4, 2, "unexpected character '1'"));
+}
+
+fn part1(groups: &[Group]) -> usize {
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .flatten()
+                .collect::<HashSet<_>>()
+                .len()
+        })
+        .sum()
+}
+
+fn part2(groups: &[Group]) -> usize {
+    groups
+        .iter()
+        .map(|group| {
+            group
+                .iter()
+                .cloned()
+                .fold_first(|a, b| a.intersection(&b).copied().collect())
+                .unwrap()
+                .len()
+        })
+        .sum()
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 6;
+
+    type Input = Vec<Group>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Group>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Group>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Group>) -> usize {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day06,
+    example_0 { 1, 2 },
+    example_1 { 1, 2 },
+    input { 1 => 6596, 2 => 3219 },
+}
@@ -0,0 +1,123 @@
+use aoc::{parse, ParseError, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Color(String);
+
+pub struct Rule {
+    outer: Color,
+    inner: Vec<(u64, Color)>,
+}
+
+lazy_static! {
+    static ref RULE_RE: Regex = Regex::new(r"^(.*?) bags contain (.*)\.$").unwrap();
+    static ref INNER_RE: Regex = Regex::new(r"^(\d+) (.*?) bags?$").unwrap();
+}
+
+impl FromStr for Rule {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let caps = parse::captures(&RULE_RE, s)?;
+        let outer = Color(caps.str(1)?.to_string());
+        let inner = caps.parse(2, |inner_str| {
+            if inner_str == "no other bags" {
+                Ok(vec![])
+            } else {
+                parse::separated(inner_str, ",", |s| {
+                    let caps = parse::captures(&INNER_RE, s)?;
+                    Ok((caps.get(1)?, Color(caps.str(2)?.to_string())))
+                })
+            }
+        })?;
+        Ok(Rule {
+            outer,
+            inner,
+        })
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Rule>, ParseError> {
+    parse::lines(input, str::parse)
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        parse("faded blue bags contain no other bags.\nbright white bags contain 1 shiny gold bag, x dark olive bags.\n").err(),
+        Some(ParseError::new(2, 45, "expected a line matching ^(\\d+) (.*?) bags?$, found \"x dark olive bags\"")));
+}
+
+fn part1(rules: &[Rule]) -> usize {
+    let mut inner_to_outer = HashMap::<&Color, Vec<&Color>>::new();
+    for rule in rules {
+        for (_, inner) in &rule.inner {
+            inner_to_outer.entry(&inner).or_default().push(&rule.outer);
+            inner_to_outer.entry(&rule.outer).or_default();
+        }
+    }
+
+    let innermost = Color("shiny gold".to_string());
+    let mut outermost = HashSet::<&Color>::new();
+    let mut stack = vec![&innermost];
+    while let Some(curr) = stack.pop() {
+        if !outermost.contains(curr) {
+            outermost.insert(curr);
+            for outer in inner_to_outer.get(curr).unwrap() {
+                stack.push(outer);
+            }
+        }
+    }
+
+    outermost.len() - 1 // "shiny gold" is not counted as containing itself.
+}
+
+fn count_contained_bags(outer: &Color, outer_to_rule: &HashMap<&Color, &Rule>) -> u64 {
+    let rule = outer_to_rule.get(outer).unwrap();
+    rule.inner
+        .iter()
+        .map(|(count, color)| {
+            count * (1 + count_contained_bags(color, outer_to_rule))
+        })
+        .sum()
+}
+
+fn part2(rules: &[Rule]) -> u64 {
+    let outer_to_rule = rules
+        .iter()
+        .map(|rule| (&rule.outer, rule))
+        .collect::<HashMap<_, _>>();
+    count_contained_bags(&Color("shiny gold".to_string()), &outer_to_rule)
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 7;
+
+    type Input = Vec<Rule>;
+    type Output1 = usize;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Rule>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Rule>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Rule>) -> u64 {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day07,
+    example_0 { 1, 2 },
+    example_1 { 2 },
+    input { 1 => 142 },
+}
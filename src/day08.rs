@@ -0,0 +1,330 @@
+use aoc::{parse, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+#[derive(Debug, Copy, Clone)]
+pub enum Instruction {
+    Acc(i64),
+    Jmp(i64),
+    Nop(i64),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let parts = s.split(" ").collect::<Vec<_>>();
+        if parts.len() != 2 {
+            return Err(ParseError::at(s, s, "expected an operation and an argument"));
+        }
+        let op = parts[0];
+        let arg = parts[1].parse::<i64>()
+            .map_err(|err| ParseError::at(s, parts[1], format!("invalid argument {:?}: {}", parts[1], err)))?;
+        use Instruction::*;
+        Ok(match op {
+            "acc" => Acc(arg),
+            "jmp" => Jmp(arg),
+            "nop" => Nop(arg),
+            _ => return Err(ParseError::at(s, op, format!("unknown operation {:?}", op))),
+        })
+    }
+}
+
+impl Instruction {
+    fn next_offset(self) -> i64 {
+        use Instruction::*;
+        match self {
+            Jmp(arg) => arg,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Program(Vec<Instruction>);
+
+impl FromStr for Program {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        parse::lines(s, str::parse).map(Program)
+    }
+}
+
+impl Index<i64> for Program {
+    type Output = Instruction;
+    fn index(&self, pc: i64) -> &Self::Output {
+        &self.0[pc as usize]
+    }
+}
+
+impl IndexMut<i64> for Program {
+    fn index_mut(&mut self, pc: i64) -> &mut Self::Output {
+        &mut self.0[pc as usize]
+    }
+}
+
+impl Program {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn enumerate<'a>(&'a self) -> impl Iterator<Item = (i64, Instruction)> + 'a {
+        self.0.iter().enumerate().map(|(pc, instr)| (pc as i64, *instr))
+    }
+}
+
+#[derive(Debug)]
+struct Interpreter {
+    prog: Program,
+    pc: i64,
+    acc: i64,
+}
+
+impl Interpreter {
+    fn new(prog: Program) -> Self {
+        Interpreter {
+            prog,
+            pc: 0,
+            acc: 0,
+        }
+    }
+
+    fn curr_instr(&self) -> Instruction {
+        self.prog[self.pc]
+    }
+
+    fn step(&mut self) {
+        let instr = self.curr_instr();
+        use Instruction::*;
+        match instr {
+            Acc(arg) => { self.acc += arg; },
+            Jmp(_) => {},
+            Nop(_) => {},
+        };
+        self.pc += instr.next_offset();
+    }
+}
+
+fn parse(input: &str) -> Result<Program, ParseError> {
+    input.parse()
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        parse("nop +0\nacc +1\nmul +2\n").unwrap_err(),
+        ParseError::new(3, 1, "unknown operation \"mul\""));
+    assert!(parse("nop +0\njmp x1").unwrap_err().to_string().starts_with("line 2, column 5: invalid argument"));
+    assert_eq!(parse("nop\n").unwrap_err(), ParseError::new(1, 1, "expected an operation and an argument"));
+}
+
+fn part1(prog: &Program) -> i64 {
+    let mut ip = Interpreter::new(prog.clone());
+    let mut visited = HashSet::new();
+    while !visited.contains(&ip.pc) {
+        visited.insert(ip.pc);
+        ip.step();
+    }
+    ip.acc
+}
+
+fn uncorrupt(instr: Instruction) -> Instruction {
+    use Instruction::*;
+    match instr {
+        Jmp(arg) => Nop(arg),
+        Nop(arg) => Jmp(arg),
+        _ => instr,
+    }
+}
+
+fn part2(prog: &Program) -> i64 {
+    let final_pc = prog.len() as i64;
+
+    let mut come_from = HashMap::<i64, Vec<i64>>::new();
+    for (pc, instr) in prog.enumerate() {
+        let next_pc = pc + instr.next_offset();
+        come_from.entry(next_pc).or_default().push(pc);
+    }
+
+    let mut leads_to_end = HashSet::new();
+    let mut stack = vec![final_pc];
+    while let Some(pc) = stack.pop() {
+        if !leads_to_end.contains(&pc) {
+            leads_to_end.insert(pc);
+            if let Some(froms) = come_from.get(&pc) {
+                for &from in froms {
+                    stack.push(from);
+                }
+            }
+        }
+    }
+
+    let mut ip = Interpreter::new(prog.clone());
+    let mut patched = false;
+    while ip.pc != final_pc {
+        if !patched {
+            let uncorrupted_instr = uncorrupt(ip.curr_instr());
+            if leads_to_end.contains(&(ip.pc + uncorrupted_instr.next_offset())) {
+                ip.prog[ip.pc] = uncorrupted_instr;
+                patched = true;
+            }
+        }
+        ip.step();
+    }
+    ip.acc
+}
+
+/// Runs the program until it loops or ends, and returns the accumulator if it ends.
+#[cfg(test)]
+fn run_to_end(prog: &Program) -> Option<i64> {
+    let mut ip = Interpreter::new(prog.clone());
+    let mut visited = HashSet::new();
+    while ip.pc != prog.len() as i64 {
+        if !visited.insert(ip.pc) {
+            return None;
+        }
+        ip.step();
+    }
+    Some(ip.acc)
+}
+
+/// The accumulator at the end of each program that ends after uncorrupting one instruction.
+#[cfg(test)]
+fn fixes(prog: &Program) -> Vec<i64> {
+    (0..prog.len() as i64)
+        .filter_map(|pc| match prog[pc] {
+            Instruction::Acc(_) => None,
+            instr => {
+                let mut fixed = prog.clone();
+                fixed[pc] = uncorrupt(instr);
+                run_to_end(&fixed)
+            },
+        })
+        .collect()
+}
+
+/// A valid puzzle input: every jump stays inside the program or lands right after it, even when
+/// uncorrupted, the program loops, and exactly one uncorrupted instruction makes it end.
+#[cfg(test)]
+fn is_valid(prog: &Program) -> bool {
+    let len = prog.len() as i64;
+    prog.enumerate().all(|(pc, instr)| match instr {
+        Instruction::Acc(_) => true,
+        Instruction::Jmp(arg) | Instruction::Nop(arg) => (0..=len).contains(&(pc + arg)),
+    }) && run_to_end(prog).is_none() && fixes(prog).len() == 1
+}
+
+/// Checks the reverse reachability in `part2` against trying every fix.
+#[cfg(test)]
+struct Part2Differential;
+
+#[cfg(test)]
+impl aoc::differential::Differential for Part2Differential {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 8;
+    const NAME: &'static str = "part2";
+
+    type Case = Program;
+    type Output = i64;
+
+    fn generate(rng: &mut aoc::random::Rng) -> Program {
+        loop {
+            let len = rng.range(2..12);
+            let prog = Program((0..len)
+                .map(|pc| match rng.index(3) {
+                    0 => Instruction::Acc(rng.range(-9..10)),
+                    1 => Instruction::Jmp(rng.range(-pc..len - pc + 1)),
+                    _ => Instruction::Nop(rng.range(-pc..len - pc + 1)),
+                })
+                .collect());
+            if is_valid(&prog) {
+                return prog;
+            }
+        }
+    }
+
+    fn shrink(prog: &Program) -> Vec<Program> {
+        let simpler_args = (0..prog.len() as i64).filter_map(|pc| match prog[pc] {
+            Instruction::Acc(arg) if arg != 0 => {
+                let mut simpler = prog.clone();
+                simpler[pc] = Instruction::Acc(arg / 2);
+                Some(simpler)
+            },
+            _ => None,
+        });
+        // Remove each instruction, keeping the other jumps pointing at the same instructions.
+        let removals = (0..prog.len() as i64).rev().map(|removed| {
+            let moved = |pc: i64| if pc > removed { pc - 1 } else { pc };
+            Program(prog.enumerate()
+                .filter(|&(pc, _)| pc != removed)
+                .map(|(pc, instr)| match instr {
+                    Instruction::Acc(_) => instr,
+                    Instruction::Jmp(arg) => Instruction::Jmp(moved(pc + arg) - moved(pc)),
+                    Instruction::Nop(arg) => Instruction::Nop(moved(pc + arg) - moved(pc)),
+                })
+                .collect())
+        });
+        removals
+            .chain(simpler_args)
+            .filter(is_valid)
+            .collect()
+    }
+
+    fn optimized(prog: &Program) -> i64 {
+        part2(prog)
+    }
+
+    fn reference(prog: &Program) -> i64 {
+        fixes(prog)[0]
+    }
+
+    fn to_input(prog: &Program) -> String {
+        prog.0.iter()
+            .map(|instr| match instr {
+                Instruction::Acc(arg) => format!("acc {:+}\n", arg),
+                Instruction::Jmp(arg) => format!("jmp {:+}\n", arg),
+                Instruction::Nop(arg) => format!("nop {:+}\n", arg),
+            })
+            .collect()
+    }
+
+    fn from_input(input: &str) -> Result<Program, ParseError> {
+        parse(input)
+    }
+}
+
+#[test]
+fn test_part2_differential() {
+    aoc::differential::check::<Part2Differential>(200);
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 8;
+
+    type Input = Program;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Program, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Program) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Program) -> i64 {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day08,
+    example_0 { 1, 2 },
+    input { 1 => 1801, 2 => 2060 },
+}
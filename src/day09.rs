@@ -0,0 +1,92 @@
+use aoc::{parse, ParseError, Solution};
+use multiset::HashMultiSet;
+
+fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    parse::numbers(input)
+}
+
+fn first_non_sum(numbers: &[u64], k: usize) -> u64 {
+    let mut set = HashMultiSet::new();
+    for i in 0..k {
+        set.insert(numbers[i]);
+    }
+    for i in k..numbers.len() {
+        let n = numbers[i];
+        let is_sum = ((i - k)..i)
+            .map(|ia| numbers[ia])
+            .filter(|&a| a <= n && a + a != n && set.contains(&(n - a)))
+            .next()
+            .is_some();
+        if !is_sum {
+            return n;
+        }
+        set.remove(&numbers[i - k]);
+        set.insert(n);
+    }
+    panic!();
+}
+
+fn part1(numbers: &[u64]) -> u64 {
+    first_non_sum(numbers, 25)
+}
+
+#[test]
+fn test_part1() {
+    aoc::solution_tests::check_example::<Day09, _, _>(0, 1, |input| first_non_sum(&parse(input).unwrap(), 5));
+}
+
+fn weakness(numbers: &[u64], k: usize) -> u64 {
+    let target = first_non_sum(numbers, k);
+    let mut start = 0;
+    let mut end = 0;
+    let mut sum = 0;
+    while start < numbers.len() {
+        if sum < target {
+            sum += numbers[end];
+            end += 1;
+        } else if sum > target {
+            sum -= numbers[start];
+            start += 1;
+        } else {
+            return numbers[start..end].iter().min().unwrap() + numbers[start..end].iter().max().unwrap();
+        }
+    }
+    panic!();
+}
+
+fn part2(numbers: &[u64]) -> u64 {
+    weakness(numbers, 25)
+}
+
+#[test]
+fn test_part2() {
+    aoc::solution_tests::check_example::<Day09, _, _>(0, 2, |input| weakness(&parse(input).unwrap(), 5));
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 9;
+
+    type Input = Vec<u64>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<u64>) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<u64>) -> u64 {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day09,
+    input { 1 => 31161678 },
+}
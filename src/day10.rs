@@ -0,0 +1,56 @@
+use aoc::{parse, ParseError, Solution};
+
+fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    parse::numbers(input)
+}
+
+fn part1(adapters: &[u64]) -> u64 {
+    let mut adapters = adapters.to_vec();
+    adapters.push(0);
+    adapters.sort();
+    adapters.push(*adapters.last().unwrap() + 3);
+    let diffs: Vec<u64> = adapters.iter().zip(&adapters[1..]).map(|(a, b)| b - a).collect();
+    diffs.iter().filter(|&&d| d == 1).count() as u64 * diffs.iter().filter(|&&d| d == 3).count() as u64
+}
+
+fn part2(adapters: &[u64]) -> u64 {
+    let mut adapters = adapters.to_vec();
+    adapters.sort();
+    let mut combinations = vec![0; *adapters.last().unwrap() as usize + 4];
+    combinations[3] = 1;
+    for a in adapters {
+        let idx = (a + 3) as usize;
+        combinations[idx] = combinations[idx - 1] + combinations[idx - 2] + combinations[idx - 3];
+    }
+    *combinations.last().unwrap()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 10;
+
+    type Input = Vec<u64>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<u64>) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<u64>) -> u64 {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day10,
+    example_0 { 1, 2 },
+    example_1 { 1, 2 },
+    input { 1 => 2030, 2 => 42313823813632 },
+}
@@ -0,0 +1,96 @@
+use aoc::automaton::{Automaton, Dense, Graph};
+use aoc::{parse, Grid, ParseError, Solution, DIRECTIONS8};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Floor,
+    EmptySeat,
+    OccupiedSeat,
+}
+
+impl Cell {
+    fn from_char(c: char) -> Option<Cell> {
+        match c {
+            '.' => Some(Cell::Floor),
+            'L' => Some(Cell::EmptySeat),
+            '#' => Some(Cell::OccupiedSeat),
+            _ => None,
+        }
+    }
+}
+
+type Board = Grid<Cell>;
+
+/// Runs the seating rules on the seats, where each seat only looks at the seats returned by
+/// `visible`, until nobody moves any more. Returns the number of occupied seats.
+fn settle<F>(board: &Board, max_occupied: usize, visible: F) -> usize
+    where F: Fn(i64, i64) -> Vec<(i64, i64)>
+{
+    let mut num_seats = 0;
+    let seat_indices = board.map(|&cell| {
+        if cell == Cell::Floor {
+            return None;
+        }
+        num_seats += 1;
+        Some(num_seats - 1)
+    });
+    let seats = || board.enumerate().filter(|&(_, &cell)| cell != Cell::Floor);
+    let neighbors = seats()
+        .map(|((x, y), _)| visible(x, y).into_iter().filter_map(|p| seat_indices[p]).collect())
+        .collect();
+    let occupied = seats()
+        .enumerate()
+        .filter(|&(_, (_, &cell))| cell == Cell::OccupiedSeat)
+        .map(|(i, _)| i);
+
+    let mut seating = Dense::new(Graph { neighbors }, occupied);
+    seating.run_to_fixed_point(&|occupied, occupied_neighbors| {
+        if occupied { occupied_neighbors < max_occupied } else { occupied_neighbors == 0 }
+    });
+    seating.population()
+}
+
+fn parse(input: &str) -> Result<Board, ParseError> {
+    parse::grid(input, Cell::from_char)
+}
+
+fn part1(board: &Board) -> usize {
+    settle(board, 4, |x, y| board.neighbors8(x, y).collect())
+}
+
+fn part2(board: &Board) -> usize {
+    settle(board, 5, |x, y| {
+        DIRECTIONS8.iter()
+            .filter_map(|&(dx, dy)| board.ray(x, y, dx, dy).find(|&p| board[p] != Cell::Floor))
+            .collect()
+    })
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 11;
+
+    type Input = Board;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Board, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Board) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Board) -> usize {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day11,
+    example_0 { 1, 2 },
+    input { 1 => 2247, 2 => 2011 },
+}
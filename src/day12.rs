@@ -0,0 +1,122 @@
+use aoc::{parse, ParseError, Solution};
+
+#[derive(Clone, Copy)]
+pub struct Instr {
+    op: u8,
+    arg: i64,
+}
+
+fn parse_instr(line: &str) -> Result<Instr, ParseError> {
+    let op = match line.bytes().next() {
+        Some(op) if b"NSEWLRF".contains(&op) => op,
+        _ => return Err(ParseError::at(line, line, "expected one of N, S, E, W, L, R, F")),
+    };
+    let arg = parse::value::<i64>(&line[1..]).map_err(|err| err.within(line, &line[1..]))?;
+    if (op == b'L' || op == b'R') && arg % 90 != 0 {
+        return Err(ParseError::at(line, &line[1..], "expected a multiple of 90 degrees"));
+    }
+    Ok(Instr { op, arg })
+}
+
+struct State {
+    x: i64,
+    y: i64,
+    dx: i64,
+    dy: i64,
+}
+
+impl State {
+    fn rotate_dir(&mut self, angle: i64) {
+        let (dx, dy) = match angle.rem_euclid(360) {
+            0 => (self.dx, self.dy),
+            90 => (self.dy, -self.dx),
+            180 => (-self.dx, -self.dy),
+            270 => (-self.dy, self.dx),
+            _ => panic!(),
+        };
+        self.dx = dx;
+        self.dy = dy;
+    }
+
+    fn move_forward(&mut self, steps: i64) {
+        self.x += self.dx * steps;
+        self.y += self.dy * steps;
+    }
+
+    fn manhattan_distance(&self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Instr>, ParseError> {
+    parse::lines(input, parse_instr)
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(parse("F10\nX3\n").err(), Some(ParseError::new(2, 1, "expected one of N, S, E, W, L, R, F")));
+    assert_eq!(parse("F10\nR45\n").err(), Some(ParseError::new(2, 2, "expected a multiple of 90 degrees")));
+}
+
+fn part1(instrs: &[Instr]) -> i64 {
+    let mut state = State { x: 0, y: 0, dx: 1, dy: 0 };
+    for &Instr { op, arg } in instrs {
+        match op {
+            b'N' => state.y += arg,
+            b'S' => state.y -= arg,
+            b'E' => state.x += arg,
+            b'W' => state.x -= arg,
+            b'L' => state.rotate_dir(-arg),
+            b'R' => state.rotate_dir(arg),
+            b'F' => state.move_forward(arg),
+            _ => panic!(),
+        };
+    }
+    state.manhattan_distance()
+}
+
+fn part2(instrs: &[Instr]) -> i64 {
+    let mut state = State { x: 0, y: 0, dx: 10, dy: 1 };
+    for &Instr { op, arg } in instrs {
+        match op {
+            b'N' => state.dy += arg,
+            b'S' => state.dy -= arg,
+            b'E' => state.dx += arg,
+            b'W' => state.dx -= arg,
+            b'L' => state.rotate_dir(-arg),
+            b'R' => state.rotate_dir(arg),
+            b'F' => state.move_forward(arg),
+            _ => panic!(),
+        };
+    }
+    state.manhattan_distance()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 12;
+
+    type Input = Vec<Instr>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Instr>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Instr>) -> i64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Instr>) -> i64 {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day12,
+    example_0 { 1, 2 },
+    input { 1 => 2228, 2 => 42908 },
+}
@@ -0,0 +1,89 @@
+use aoc::math::{self, InconsistentSystem};
+use aoc::{parse, ParseError, Solution};
+
+pub struct Notes {
+    earliest: i64,
+    /// Bus ids, or `None` for buses that are out of service.
+    buses: Vec<Option<i64>>,
+}
+
+fn parse(input: &str) -> Result<Notes, ParseError> {
+    let (earliest, buses) = parse::split_once(input, "\n")?;
+    Ok(Notes {
+        earliest: parse::value(earliest)?,
+        buses: parse::separated(buses, ",", |bus| match bus {
+            "x" => Ok(None),
+            _ => match parse::value(bus)? {
+                id if id > 0 => Ok(Some(id)),
+                _ => Err(ParseError::at(bus, bus, "expected a positive bus id")),
+            },
+        }).map_err(|err| err.within(input, buses))?,
+    })
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(parse("939\n7,13,x,y\n").err(), Some(ParseError::new(2, 8, "invalid value \"y\": invalid digit found in string")));
+    assert_eq!(parse("939\n7,0\n").err(), Some(ParseError::new(2, 3, "expected a positive bus id")));
+}
+
+fn part1(notes: &Notes) -> Result<i64, &'static str> {
+    let t = notes.earliest;
+    let (wait, id) = notes.buses.iter()
+        .filter_map(|&id| id)
+        .map(|id| ((-t).rem_euclid(id), id))
+        .min().ok_or("no buses in service")?;
+    Ok(id * wait)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&parse("939\nx,x").unwrap()), Err("no buses in service"));
+}
+
+fn part2(notes: &Notes) -> Result<i128, InconsistentSystem> {
+    let congruences = notes.buses.iter()
+        .zip(0i128..)
+        .filter_map(|(&id, i)| id.map(|n| (-i, n as i128)))
+        .collect::<Vec<_>>();
+    math::crt(&congruences).map(|(t, _)| t)
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(&parse("0\n17,x,13,19").unwrap()), Ok(3417));
+    assert_eq!(part2(&parse("0\n67,7,59,61").unwrap()), Ok(754018));
+    assert_eq!(part2(&parse("0\n67,x,7,59,61").unwrap()), Ok(779210));
+    assert_eq!(part2(&parse("0\n67,7,x,59,61").unwrap()), Ok(1261476));
+    assert_eq!(part2(&parse("0\n1789,37,47,1889").unwrap()), Ok(1202161486));
+    assert_eq!(part2(&parse("0\n4,6").unwrap()), Err(InconsistentSystem { index: 1 }));
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 13;
+
+    type Input = Notes;
+    type Output1 = Result<i64, &'static str>;
+    type Output2 = Result<i128, InconsistentSystem>;
+
+    fn parse(input: &str) -> Result<Notes, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Notes) -> Result<i64, &'static str> {
+        part1(input)
+    }
+
+    fn part2(input: &Notes) -> Result<i128, InconsistentSystem> {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day13,
+    example_0 { 1, 2 },
+    input { 1 => 3385, 2 => 600689120448303 },
+}
@@ -1,3 +1,4 @@
+use aoc::{parse, ParseError, Solution};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
@@ -7,7 +8,8 @@ lazy_static! {
     static ref MEM_RE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
 }
 
-enum Instr {
+#[derive(Clone, Copy)]
+pub enum Instr {
     Mask { zero: u64, one: u64, x: u64 },
     Mem { addr: u64, val: u64 },
 }
@@ -20,27 +22,38 @@ fn mask_bits(mask: &str, chr: u8) -> u64 {
         .fold(0, |a, b| a | b)
 }
 
-fn parse_instr(line: &str) -> Instr {
-    if let Some(caps) = MEM_RE.captures(line) {
-        Mem {
-            addr: caps.get(1).unwrap().as_str().parse::<u64>().unwrap(),
-            val: caps.get(2).unwrap().as_str().parse::<u64>().unwrap(),
-        }
-    } else if let Some(caps) = MASK_RE.captures(line) {
-        let mask = caps.get(1).unwrap().as_str();
-        Mask {
+fn parse_instr(line: &str) -> Result<Instr, ParseError> {
+    if let Ok(caps) = parse::captures(&MEM_RE, line) {
+        Ok(Mem { addr: caps.get(1)?, val: caps.get(2)? })
+    } else if let Ok(caps) = parse::captures(&MASK_RE, line) {
+        let mask = caps.str(1)?;
+        Ok(Mask {
             zero: mask_bits(mask, b'0'),
             one: mask_bits(mask, b'1'),
             x: mask_bits(mask, b'X'),
-        }
+        })
     } else {
-        panic!()
+        Err(ParseError::at(line, line, "expected \"mask = ...\" or \"mem[...] = ...\""))
     }
 }
 
-fn part1(input: &str) -> u64 {
-    input.lines().map(parse_instr)
-        .fold((0, 0, HashMap::new()), |(zero_mask, one_mask, mut mem), instr| {
+fn parse(input: &str) -> Result<Vec<Instr>, ParseError> {
+    parse::lines(input, parse_instr)
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        parse("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X\nmem[8] = 11\nmem[7] 101\n").err(),
+        Some(ParseError::new(3, 1, "expected \"mask = ...\" or \"mem[...] = ...\"")));
+    assert_eq!(
+        parse("mem[8] = 99999999999999999999\n").err(),
+        Some(ParseError::new(1, 10, "invalid value \"99999999999999999999\": number too large to fit in target type")));
+}
+
+fn part1(instrs: &[Instr]) -> u64 {
+    instrs.iter()
+        .fold((0, 0, HashMap::new()), |(zero_mask, one_mask, mut mem), &instr| {
             match instr {
                 Mask { zero, one, .. } => (zero, one, mem),
                 Mem { addr, val } => {
@@ -52,12 +65,6 @@ fn part1(input: &str) -> u64 {
         .2.values().sum()
 }
 
-#[test]
-fn test_part1() {
-    assert_eq!(part1(&aoc::example(0)), 165);
-    assert_eq!(part1(&aoc::input()), 17765746710228);
-}
-
 fn set_floating(mem: &mut HashMap<u64, u64>, addr: u64, floating_mask: u64, val: u64) {
     if floating_mask == 0 {
         mem.insert(addr, val);
@@ -68,9 +75,9 @@ fn set_floating(mem: &mut HashMap<u64, u64>, addr: u64, floating_mask: u64, val:
     }
 }
 
-fn part2(input: &str) -> u64 {
-    input.lines().map(parse_instr)
-        .fold((0, 0, HashMap::new()), |(one_mask, floating_mask, mut mem), instr| {
+fn part2(instrs: &[Instr]) -> u64 {
+    instrs.iter()
+        .fold((0, 0, HashMap::new()), |(one_mask, floating_mask, mut mem), &instr| {
             match instr {
                 Mask { one, x, .. } => (one, x, mem),
                 Mem { addr, val } => {
@@ -82,12 +89,32 @@ fn part2(input: &str) -> u64 {
         .2.values().sum()
 }
 
-#[test]
-fn test_part2() {
-    assert_eq!(part2(&aoc::example(4)), 208);
-    assert_eq!(part2(&aoc::input()), 4401465949086);
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 14;
+
+    type Input = Vec<Instr>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<Instr>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Instr>) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Instr>) -> u64 {
+        part2(input)
+    }
 }
 
-fn main() {
-    aoc::main(part1, part2);
+aoc::solution_tests! {
+    Day14,
+    example_0 { 1 },
+    example_4 { 2 },
+    input { 1 => 17765746710228, 2 => 4401465949086 },
 }
@@ -0,0 +1,88 @@
+use aoc::{parse, ParseError, Solution};
+use std::collections::HashMap;
+
+fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    parse::list(input, ",")
+}
+
+fn play(numbers: &[u64], num_turns: u64) -> u64 {
+    let mut list = numbers.iter().copied();
+    let mut mem = HashMap::<u64, u64>::new();
+    let mut last_spoken = 0;
+    for turn in 1.. {
+        let spoken = if let Some(from_list) = list.next() {
+            from_list
+        } else {
+            match mem.get(&last_spoken) {
+                None => 0,
+                Some(m) => turn - 1 - m,
+            }
+        };
+        if turn == num_turns {
+            return spoken;
+        }
+        if turn > 1 {
+            mem.insert(last_spoken, turn - 1);
+        }
+        last_spoken = spoken;
+    }
+    panic!()
+}
+
+fn part1(numbers: &[u64]) -> u64 {
+    play(numbers, 2020)
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&parse("0,3,6").unwrap()), 436);
+    assert_eq!(part1(&parse("1,3,2").unwrap()), 1);
+    assert_eq!(part1(&parse("2,1,3").unwrap()), 10);
+    assert_eq!(part1(&parse("1,2,3").unwrap()), 27);
+    assert_eq!(part1(&parse("2,3,1").unwrap()), 78);
+    assert_eq!(part1(&parse("3,2,1").unwrap()), 438);
+    assert_eq!(part1(&parse("3,1,2").unwrap()), 1836);
+}
+
+fn part2(numbers: &[u64]) -> u64 {
+    play(numbers, 30000000)
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2(&parse("0,3,6").unwrap()), 175594);
+    assert_eq!(part2(&parse("1,3,2").unwrap()), 2578);
+    assert_eq!(part2(&parse("2,1,3").unwrap()), 3544142);
+    assert_eq!(part2(&parse("1,2,3").unwrap()), 261214);
+    assert_eq!(part2(&parse("2,3,1").unwrap()), 6895259);
+    assert_eq!(part2(&parse("3,2,1").unwrap()), 18);
+    assert_eq!(part2(&parse("3,1,2").unwrap()), 362);
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 15;
+
+    type Input = Vec<u64>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<u64>) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<u64>) -> u64 {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day15,
+    input { 1 => 257, 2 => 8546398 },
+}
@@ -0,0 +1,145 @@
+use aoc::{parse, ParseError, Solution};
+use bit_set::BitSet;
+use std::collections::HashMap;
+
+pub struct Notes {
+    fields: Vec<(String, BitSet)>,
+    my_ticket: Vec<usize>,
+    nearby_tickets: Vec<Vec<usize>>,
+}
+
+fn parse_rule(line: &str) -> Result<(String, BitSet), ParseError> {
+    let (name, ranges) = parse::split_once(line, ": ")?;
+    let valid = parse::separated::<_, Vec<_>, _>(ranges, " or ", |range| {
+            let (lo, hi) = parse::split_once(range, "-")?;
+            let lo = parse::value::<usize>(lo)?;
+            let hi = parse::value::<usize>(hi).map_err(|err| err.within(range, hi))? + 1;
+            Ok((lo..hi).collect::<BitSet>())
+        })
+        .map_err(|err| err.within(line, ranges))?
+        .into_iter()
+        .fold(BitSet::with_capacity(1024), |a, b| a.union(&b).collect());
+    Ok((name.to_string(), valid))
+}
+
+fn parse_tickets(section: &str, header: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let (first_line, tickets) = parse::split_once(section, "\n")?;
+    if first_line != header {
+        return Err(ParseError::at(section, first_line, format!("expected {:?}", header)));
+    }
+    parse::lines(tickets, |line| parse::list(line, ",")).map_err(|err| err.within(section, tickets))
+}
+
+fn parse(input: &str) -> Result<Notes, ParseError> {
+    let sections = parse::blocks::<_, Vec<_>, _>(input, Ok)?;
+    if sections.len() != 3 {
+        return Err(ParseError::at(input, input, "expected rules, your ticket and nearby tickets"));
+    }
+    let fields = parse::lines(sections[0], parse_rule).map_err(|err| err.within(input, sections[0]))?;
+    let mut my_tickets = parse_tickets(sections[1], "your ticket:").map_err(|err| err.within(input, sections[1]))?;
+    let nearby_tickets = parse_tickets(sections[2], "nearby tickets:").map_err(|err| err.within(input, sections[2]))?;
+    if my_tickets.len() != 1 {
+        return Err(ParseError::at(input, sections[1], "expected a single ticket"));
+    }
+    Ok(Notes { fields, my_ticket: my_tickets.remove(0), nearby_tickets })
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        parse("class: 1-3 or 5-7\nrow: 6-11 or 33-x\n\nyour ticket:\n7,1,14\n\nnearby tickets:\n7,3,47\n").err(),
+        Some(ParseError::new(2, 17, "invalid value \"x\": invalid digit found in string")));
+    assert_eq!(
+        parse("class: 1-3 or 5-7\n\nyour ticket:\n7,1,14\n\nnearby:\n7,3,47\n").err(),
+        Some(ParseError::new(6, 1, "expected \"nearby tickets:\"")));
+}
+
+fn part1(notes: &Notes) -> usize {
+    let valid_for_any_field = notes.fields
+        .iter()
+        .map(|(_name, valid)| valid)
+        .fold(BitSet::with_capacity(1024), |a, b| a.union(b).collect::<BitSet>());
+    notes.nearby_tickets
+        .iter()
+        .flatten()
+        .filter(|&&field| !valid_for_any_field.contains(field))
+        .sum()
+}
+
+fn part2(notes: &Notes) -> usize {
+    let fields = &notes.fields;
+    let valid_for_any_field = fields
+        .iter()
+        .map(|(_name, valid)| valid)
+        .fold(BitSet::with_capacity(1024), |a, b| a.union(b).collect::<BitSet>());
+
+    let n = fields.len();
+    let mut candidates = (0..n)
+        .map(|_| (0..n).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    for ticket in &notes.nearby_tickets {
+        if !ticket.iter().all(|&field| valid_for_any_field.contains(field)) {
+            continue;
+        }
+        for (i, &f) in ticket.iter().enumerate() {
+            let cand = &mut candidates[i];
+            let mut j = 0;
+            while j < cand.len() {
+                if !fields[cand[j]].1.contains(f) {
+                    cand.swap_remove(j);
+                } else {
+                    j += 1;
+                }
+            }
+        }
+    }
+
+    let mut ticket_indices_for_field = HashMap::<String, usize>::new();
+    while let Some(ticket_index) = candidates.iter().position(|c| c.len() == 1) {
+        let field_index = *candidates[ticket_index].iter().next().unwrap();
+        let field_name = &fields[field_index].0;
+        assert!(!ticket_indices_for_field.contains_key(field_name));
+        ticket_indices_for_field.insert(field_name.to_string(), ticket_index);
+        for c in candidates.iter_mut() {
+            if let Some(ci) = c.iter().position(|&fi| fi == field_index) {
+                c.swap_remove(ci);
+            }
+        }
+    }
+    assert!(ticket_indices_for_field.len() == n);
+    
+    fields
+        .iter()
+        .filter(|(name, _)| name.starts_with("departure"))
+        .map(|(name, _)| notes.my_ticket[*ticket_indices_for_field.get(name).unwrap()])
+        .product()
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 16;
+
+    type Input = Notes;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Notes, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Notes) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Notes) -> usize {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day16,
+    example_1 { 1 },
+    input { 1 => 30869, 2 => 4381476149273 },
+}
@@ -0,0 +1,61 @@
+use aoc::automaton::{Automaton, Lattice, LifeRule, Sparse};
+use aoc::{parse, ParseError, Solution};
+
+/// The positions of the active cubes in the initial slice.
+type State = Vec<(i64, i64)>;
+
+fn parse(input: &str) -> Result<State, ParseError> {
+    let grid = parse::grid(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    Ok(grid
+        .enumerate()
+        .filter(|&(_, &active)| active)
+        .map(|(p, _)| p)
+        .collect())
+}
+
+fn part1(state: &[(i64, i64)]) -> usize {
+    let cubes = state.iter().map(|&(x, y)| [x, y, 0]);
+    let mut automaton = Sparse::new(Lattice::new(), cubes);
+    automaton.run(&LifeRule::conway(), 6);
+    automaton.population()
+}
+
+fn part2(state: &[(i64, i64)]) -> usize {
+    let cubes = state.iter().map(|&(x, y)| [x, y, 0, 0]);
+    let mut automaton = Sparse::new(Lattice::new(), cubes);
+    automaton.run(&LifeRule::conway(), 6);
+    automaton.population()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 17;
+
+    type Input = State;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<State, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &State) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &State) -> usize {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day17,
+    example_0 { 1, 2 },
+    input { 1 => 265, 2 => 1936 },
+}
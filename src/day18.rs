@@ -1,3 +1,5 @@
+use aoc::Solution;
+
 type Num = u64;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -179,7 +181,6 @@ fn test_part1() {
     assert_eq!(part1("5 + (8 * 3 + 9 + 3 * 4 * 3)"), 437);
     assert_eq!(part1("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), 12240);
     assert_eq!(part1("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"), 13632);
-    assert_eq!(part1(&aoc::input()), 6811433855019);
 }
 
 struct Parser2<'a> {
@@ -249,9 +250,32 @@ fn test_part2() {
     assert_eq!(part2("5 + (8 * 3 + 9 + 3 * 4 * 3)"), 1445);
     assert_eq!(part2("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))"), 669060);
     assert_eq!(part2("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2"), 23340);
-    assert_eq!(part2(&aoc::input()), 129770152447927);
 }
 
-fn main() {
-    aoc::main(part1, part2);
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 18;
+
+    type Input = String;
+    type Output1 = Num;
+    type Output2 = Num;
+
+    fn parse(input: &str) -> Result<String, aoc::ParseError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &String) -> Num {
+        part1(input)
+    }
+
+    fn part2(input: &String) -> Num {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day18,
+    input { 1 => 6811433855019, 2 => 129770152447927 },
 }
@@ -0,0 +1,141 @@
+use aoc::{parse, ParseError, Solution};
+use itertools::Itertools;
+use regex::Regex;
+use std::collections::HashMap;
+
+enum Rule {
+    Char(char),
+    Alternatives(Vec<Vec<usize>>),
+}
+
+pub struct Puzzle {
+    rules: HashMap<usize, Rule>,
+    messages: Vec<String>,
+}
+
+fn build_re(rules: &HashMap<usize, Rule>, cache: &mut HashMap<usize, String>, name: usize) -> String {
+    if !cache.contains_key(&name) {
+        let re = match rules.get(&name).unwrap() {
+            Rule::Char(c) => c.to_string(),
+            Rule::Alternatives(alts) => {
+                let re = alts.iter()
+                    .map(|alt| {
+                        alt.iter()
+                            .map(|&n| build_re(rules, cache, n))
+                            .join("")
+                    })
+                .join("|");
+                format!("(?:{})", re)
+            }
+        };
+        cache.insert(name, re);
+    }
+    cache.get(&name).unwrap().to_string()
+}
+
+fn build_full_re(rules: &HashMap<usize, Rule>) -> Regex {
+    let mut cache = HashMap::new();
+    let re = format!("^{}$", build_re(rules, &mut cache, 0));
+    Regex::new(&re).unwrap()
+}
+
+fn parse_rule(line: &str) -> Result<(usize, Rule), ParseError> {
+    let (name, expansion) = parse::split_once(line, ": ")?;
+    let name = parse::value(name)?;
+    let rule = if expansion.starts_with('"') {
+        let chars = expansion.chars().collect::<Vec<_>>();
+        if chars.len() != 3 || chars[2] != '"' {
+            return Err(ParseError::at(line, expansion, "expected a single character in quotes"));
+        }
+        Rule::Char(chars[1])
+    } else {
+        let alts = parse::separated(expansion, "|", |alt| parse::list(alt, " "))
+            .map_err(|err| err.within(line, expansion))?;
+        Rule::Alternatives(alts)
+    };
+    Ok((name, rule))
+}
+
+fn parse(input: &str) -> Result<Puzzle, ParseError> {
+    let sections = parse::blocks::<_, Vec<_>, _>(input, Ok)?;
+    if sections.len() != 2 {
+        return Err(ParseError::at(input, input, "expected rules and messages"));
+    }
+    Ok(Puzzle {
+        rules: parse::lines(sections[0], parse_rule).map_err(|err| err.within(input, sections[0]))?,
+        messages: sections[1].lines().map(str::to_string).collect(),
+    })
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        parse("0: 1 2\n1: \"a\"\n2: 1 3 | 3 x\n3: \"b\"\n\nab\n").err(),
+        Some(ParseError::new(3, 12, "invalid value \"x\": invalid digit found in string")));
+    assert_eq!(
+        parse("0: 1 2\n1: \"ab\"\n\nab\n").err(),
+        Some(ParseError::new(2, 4, "expected a single character in quotes")));
+}
+
+fn part1(puzzle: &Puzzle) -> usize {
+    let re = build_full_re(&puzzle.rules);
+    puzzle.messages.iter().filter(|line| re.is_match(line)).count()
+}
+
+fn part2(puzzle: &Puzzle) -> usize {
+    let rules = &puzzle.rules;
+    let mut cache = HashMap::new();
+    let re42_str = build_re(rules, &mut cache, 42);
+    let re31_str = build_re(rules, &mut cache, 31);
+    let re42 = Regex::new(&re42_str).unwrap();
+    let re31 = Regex::new(&re31_str).unwrap();
+    let re_fst = Regex::new(&format!("^(?:{})+$", re42)).unwrap();
+    let re_snd = Regex::new(&format!("^(?:{})+$", re31)).unwrap();
+    puzzle.messages
+        .iter()
+        .filter(|line| {
+            for i in 0..line.len() {
+                let fst = &line[0..i];
+                let snd = &line[i..];
+                if re_fst.is_match(fst) && re_snd.is_match(snd) {
+                    let re42_cnt = re42.find_iter(fst).count();
+                    let re31_cnt = re31.find_iter(snd).count();
+                    if re42_cnt > re31_cnt {
+                        return true;
+                    }
+                }
+            }
+            false
+        })
+        .count()
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 19;
+
+    type Input = Puzzle;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Puzzle, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Puzzle) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Puzzle) -> usize {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day19,
+    example_2 { 1 },
+    example_4 { 1, 2 },
+    input { 1 => 230, 2 => 341 },
+}
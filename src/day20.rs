@@ -0,0 +1,338 @@
+use aoc::random::Rng;
+use aoc::{parse, Grid, ParseError, Solution, Transformation};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashSet;
+
+type TileId = u64;
+
+pub struct Tile {
+    id: u64,
+    grid: Grid<bool>,
+    left_edge: u64,
+    right_edge: u64,
+    top_edge: u64,
+    bottom_edge: u64,
+}
+
+impl Tile {
+    fn parse(block: &str) -> Result<Tile, ParseError> {
+        lazy_static! {
+            static ref HEADER_RE: Regex = Regex::new(r"^Tile (\d+):$").unwrap();
+        }
+        let (header, rows) = parse::split_once(block, "\n")?;
+        let id = parse::captures(&HEADER_RE, header)?.get::<TileId>(1)?;
+        let grid = parse::grid(rows, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }).map_err(|err| err.within(block, rows))?;
+        if grid.width() == 0 || grid.width() != grid.height() || grid.width() > 64 {
+            return Err(ParseError::at(block, rows, "expected a square tile of at most 64 by 64"));
+        }
+
+        Ok(Tile::new(id, grid))
+    }
+
+    fn new(id: u64, grid: Grid<bool>) -> Tile {
+        let (nx, ny) = (grid.width(), grid.height());
+        let left_edge = (0..ny)
+            .map(|y| if grid[(0, y)] { 1 << y } else { 0 })
+            .fold(0, |a, b| a | b);
+        let right_edge = (0..ny)
+            .map(|y| if grid[(nx - 1, y)] { 1 << y } else { 0 })
+            .fold(0, |a, b| a | b);
+        let top_edge = (0..nx)
+            .map(|x| if grid[(x, 0)] { 1 << x } else { 0 })
+            .fold(0, |a, b| a | b);
+        let bottom_edge = (0..nx)
+            .map(|x| if grid[(x, ny - 1)] { 1 << x } else { 0 })
+            .fold(0, |a, b| a | b);
+        Tile {
+            id,
+            grid,
+            left_edge,
+            right_edge,
+            top_edge,
+            bottom_edge,
+        }
+    }
+
+    fn transformed(&self, t: Transformation) -> Tile {
+        Tile::new(self.id, self.grid.transformed(t))
+    }
+}
+
+/// Parses the tiles, and prepares all their transformations for the solver up front, so that
+/// both parts can share them.
+fn parse(input: &str) -> Result<Solver, ParseError> {
+    let tiles = parse::blocks::<_, Vec<_>, _>(input, Tile::parse)?;
+    Ok(Solver::new(&tiles))
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(parse("Tile 1:\n#.\n.#\n\nTile x:\n#.\n.#\n").err(), Some(ParseError::new(5, 1, "expected a line matching ^Tile (\\d+):$, found \"Tile x:\"")));
+    assert_eq!(parse("Tile 1:\n#.\n.#\n\nTile 2:\n#.\n.o\n").err(), Some(ParseError::new(7, 2, "unexpected character 'o'")));
+    assert_eq!(parse("Tile 1:\n#.\n.#\n.#\n").err(), Some(ParseError::new(2, 1, "expected a square tile of at most 64 by 64")));
+}
+
+pub struct Solver {
+    /// For each tile, the tile under each transformation in the order of `Transformation::all()`.
+    transformed_tiles: Vec<Vec<Tile>>,
+}
+
+struct SolveState {
+    unused_tile_indices: Vec<usize>,
+    solution: Grid<(usize, usize)>,
+}
+
+impl Solver {
+    fn new(tiles: &[Tile]) -> Solver {
+        let transformed_tiles = tiles
+            .iter()
+            .map(|tile| {
+                Transformation::all().map(|t| tile.transformed(t)).collect()
+            })
+            .collect::<Vec<Vec<Tile>>>();
+
+        Solver { transformed_tiles }
+    }
+
+    fn solve(&self) -> Grid<(usize, usize)> {
+        let unused_tile_indices = (0..self.transformed_tiles.len()).collect();
+
+        let n = self.transformed_tiles.len();
+        let nx = (n as f64).sqrt().floor() as usize;
+        let ny = nx;
+        assert_eq!(n, nx * ny);
+        let solution = Grid::new(nx as i64, ny as i64, (0, 0));
+
+        let mut state = SolveState { unused_tile_indices, solution };
+
+        if !self.solve_rec(&mut state, 0, 0) {
+            panic!();
+        }
+
+        state.solution
+    }
+
+    fn solve_rec(&self, state: &mut SolveState, x: i64, y: i64) -> bool {
+        if y >= state.solution.height() {
+            return true;
+        }
+        let num_unused = state.unused_tile_indices.len();
+        for i in 0..num_unused {
+            let tile_idx = state.unused_tile_indices[i];
+            for (t_idx, tile) in self.transformed_tiles[tile_idx].iter().enumerate() {
+                if y > 0 {
+                    let tile_top = self.tile_at(state, x, y - 1);
+                    if tile.top_edge != tile_top.bottom_edge {
+                        continue;
+                    }
+                }
+                if x > 0 {
+                    let tile_left = self.tile_at(state, x - 1, y);
+                    if tile.left_edge != tile_left.right_edge {
+                        continue;
+                    }
+                }
+                state.solution[(x, y)] = (tile_idx, t_idx);
+                let (next_x, next_y) = if x + 1 == state.solution.width() {
+                    (0, y + 1)
+                } else {
+                    (x + 1, y)
+                };
+                state.unused_tile_indices.swap_remove(i);
+                if self.solve_rec(state, next_x, next_y) {
+                    return true;
+                }
+                state.unused_tile_indices.push(tile_idx);
+                state.unused_tile_indices.swap(i, num_unused - 1);
+            }
+        }
+        false
+    }
+
+    fn tile_at(&self, state: &SolveState, x: i64, y: i64) -> &Tile {
+        self.tile(state.solution[(x, y)])
+    }
+
+    /// Returns the tile with the given index, under the transformation with the given index.
+    fn tile(&self, (tile_idx, t_idx): (usize, usize)) -> &Tile {
+        &self.transformed_tiles[tile_idx][t_idx]
+    }
+}
+
+fn part1(solver: &Solver) -> u64 {
+    let solution = solver.solve();
+
+    let (nx, ny) = (solution.width(), solution.height());
+    [(0, 0), (nx - 1, 0), (0, ny - 1), (nx - 1, ny - 1)]
+        .iter()
+        .map(|&p| solver.tile(solution[p]).id)
+        .product()
+}
+
+const SEA_MONSTER: &str = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
+
+fn part2(solver: &Solver) -> usize {
+    let solution = solver.solve();
+    let tile_size = solver.tile((0, 0)).grid.width();
+    let stride = tile_size - 2;
+    let mut grid = Grid::new(solution.width() * stride, solution.height() * stride, '.');
+    for ((x, y), &placement) in solution.enumerate() {
+        let transformed_tile = solver.tile(placement);
+        let image = transformed_tile.grid.sub_grid(1, 1, stride, stride).map(|&c| if c { '#' } else { '.' });
+        grid.blit(x * stride, y * stride, &image);
+    }
+
+    let sea_monster = SEA_MONSTER.parse::<Grid<char>>().unwrap();
+    let sea_monster_parts = sea_monster
+        .enumerate()
+        .filter(|&(_, &c)| c == '#')
+        .map(|(p, _)| p)
+        .collect::<Vec<(i64, i64)>>();
+
+    Transformation::all()
+        .map(|t| {
+            let mut transformed_grid = grid.transformed(t);
+            for y in 0..(transformed_grid.height() - sea_monster.height()) {
+                'next_x: for x in 0..(transformed_grid.width() - sea_monster.width()) {
+                    for &(mx, my) in sea_monster_parts.iter() {
+                        if transformed_grid[(x + mx, y + my)] != '#' {
+                            continue 'next_x;
+                        }
+                    }
+                    for &(mx, my) in sea_monster_parts.iter() {
+                        transformed_grid[(x + mx, y + my)] = 'O';
+                    }
+                }
+            }
+            transformed_grid.iter().filter(|&&c| c == '#').count()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Generates an image of `size` by `size` tiles with sea monsters in it, and cuts it into tiles
+/// that are flipped, rotated and shuffled. Returns the input with the answers to both parts.
+///
+/// Each edge gets a distinct pattern that is not a palindrome, nor the reverse of another edge, so
+/// the tiles fit together in only one way, as in the real input. When that takes more than 8 bits
+/// per edge, the tiles grow beyond 10 by 10.
+fn generate(rng: &mut Rng, size: usize) -> (String, u64, usize) {
+    let n = size as i64;
+    let num_edges = 2 * n * (n + 1);
+    let edge_len = (8..).find(|&len| 1 << (len - 1) >= 4 * num_edges).unwrap();
+    let tile_size = edge_len + 2;
+
+    let mut used_edges = HashSet::new();
+    let mut edge = |rng: &mut Rng| loop {
+        let bits = (0..edge_len).map(|_| rng.chance(0.5)).collect::<Vec<_>>();
+        let reversed = bits.iter().rev().copied().collect::<Vec<_>>();
+        if bits != reversed && !used_edges.contains(&reversed) && used_edges.insert(bits.clone()) {
+            return bits;
+        }
+    };
+    let horizontal_edges = Grid::from_fn(n, n + 1, |_, _| edge(rng));
+    let vertical_edges = Grid::from_fn(n + 1, n, |_, _| edge(rng));
+    let vertices = Grid::from_fn(n + 1, n + 1, |_, _| rng.chance(0.5));
+
+    let image_size = n * edge_len;
+    let mut image = Grid::from_fn(image_size, image_size, |_, _| rng.chance(0.2));
+    let sea_monster = SEA_MONSTER.parse::<Grid<char>>().unwrap();
+    let (monster_width, monster_height) = (sea_monster.width(), sea_monster.height());
+    let mut monsters = Vec::<(i64, i64)>::new();
+    if image_size > monster_width {
+        for _ in 0..image_size * image_size / 400 {
+            // Like `part2`, which doesn't look for monsters at the far right and bottom.
+            let x = rng.range(0..image_size - monster_width);
+            let y = rng.range(0..image_size - monster_height);
+            if monsters.iter().all(|&(mx, my)| (x - mx).abs() >= monster_width || (y - my).abs() >= monster_height) {
+                for ((dx, dy), _) in sea_monster.enumerate().filter(|&(_, &c)| c == '#') {
+                    image[(x + dx, y + dy)] = true;
+                }
+                monsters.push((x, y));
+            }
+        }
+    }
+    let num_monster_cells = sea_monster.iter().filter(|&&c| c == '#').count();
+    let roughness = image.iter().filter(|&&c| c).count() - num_monster_cells * monsters.len();
+
+    let max_id = 10000.max(1000 + 2 * num_edges);
+    let mut ids = HashSet::new();
+    let mut tiles = Vec::new();
+    for j in 0..n {
+        for i in 0..n {
+            let id = loop {
+                let id = rng.range(1000..max_id) as TileId;
+                if ids.insert(id) {
+                    break id;
+                }
+            };
+            let last = tile_size - 1;
+            let grid = Grid::from_fn(tile_size, tile_size, |x, y| {
+                let (edge_x, edge_y) = (i + x / last, j + y / last);
+                match (x == 0 || x == last, y == 0 || y == last) {
+                    (true, true) => vertices[(edge_x, edge_y)],
+                    (false, true) => horizontal_edges[(i, edge_y)][x as usize - 1],
+                    (true, false) => vertical_edges[(edge_x, j)][y as usize - 1],
+                    (false, false) => image[(i * edge_len + x - 1, j * edge_len + y - 1)],
+                }
+            });
+            tiles.push((id, grid.transformed(Transformation::from_index(rng.index(8)))));
+        }
+    }
+    let corners = [0, size - 1, size * (size - 1), size * size - 1].iter().map(|&k| tiles[k].0).product();
+
+    rng.shuffle(&mut tiles);
+    let input = tiles.iter()
+        .map(|(id, grid)| format!("Tile {}:\n{}", id, grid.map(|&c| if c { '#' } else { '.' })))
+        .collect::<Vec<_>>()
+        .join("\n");
+    (input, corners, roughness)
+}
+
+#[test]
+fn test_generate() {
+    for size in 1..=6 {
+        let (input, corners, roughness) = generate(&mut Rng::new(size as u64), size);
+        assert_eq!(generate(&mut Rng::new(size as u64), size).0, input);
+        let solver = parse(&input).unwrap();
+        assert_eq!((part1(&solver), part2(&solver)), (corners, roughness), "size {}", size);
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 20;
+
+    type Input = Solver;
+    type Output1 = u64;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Solver, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Solver) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Solver) -> usize {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size).0)
+    }
+}
+
+aoc::solution_tests! {
+    Day20,
+    example_0 { 1, 2 },
+    input { 1 => 64802175715999, 2 => 2146 },
+}
@@ -0,0 +1,254 @@
+use aoc::random::Rng;
+use aoc::{parse, ParseError, Solution};
+use itertools::Itertools;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+pub struct Food {
+    ingredients: Vec<String>,
+    allergens: Vec<String>,
+}
+
+lazy_static! {
+    static ref LINE_RE: Regex = Regex::new(r"^(.*) \(contains (.*)\)$").unwrap();
+}
+
+fn parse(input: &str) -> Result<Vec<Food>, ParseError> {
+    parse::lines(input, |line| {
+        let caps = parse::captures(&LINE_RE, line)?;
+        let words = |s: &str| s.split_whitespace().map(str::to_string).collect::<Vec<_>>();
+        let ingredients = words(caps.str(1)?);
+        let allergens = caps.parse(2, |s| parse::separated(s, ",", |allergen| Ok(allergen.to_string())))?;
+        Ok(Food { ingredients, allergens })
+    })
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(
+        parse("mxmxvkd kfcds (contains dairy, fish)\nsqjhc fvjkl (dairy)\n").err(),
+        Some(ParseError::new(2, 1, "expected a line matching ^(.*) \\(contains (.*)\\)$, found \"sqjhc fvjkl (dairy)\"")));
+}
+
+fn candidates(foods: &[Food]) -> HashMap<&str, Vec<&str>> {
+    let all_ingredients = foods.iter()
+        .flat_map(|food| food.ingredients.iter().map(String::as_str))
+        .collect::<Vec<&str>>();
+    let all_allergens = foods.iter()
+        .flat_map(|food| food.allergens.iter().map(String::as_str))
+        .collect::<Vec<&str>>();
+
+    let mut candidates = all_allergens.iter()
+        .map(|&allergen| (allergen, all_ingredients.clone()))
+        .collect::<HashMap<&str, Vec<&str>>>();
+    for food in foods.iter() {
+        for allergen in food.allergens.iter() {
+            candidates.get_mut(allergen.as_str()).unwrap()
+                .retain(|ingredient| food.ingredients.iter().any(|i| i == ingredient));
+        }
+    }
+
+    candidates
+}
+
+fn part1(foods: &[Food]) -> usize {
+    let candidates = candidates(foods);
+
+    let all_ingredients = foods.iter()
+        .flat_map(|food| food.ingredients.iter().map(String::as_str))
+        .collect::<HashSet<&str>>();
+
+    let mut safe_ingredients = all_ingredients.iter().copied().collect::<HashSet<&str>>();
+    for (_, ingredients) in candidates {
+        for ingredient in ingredients {
+            safe_ingredients.remove(ingredient);
+        }
+    }
+
+    foods.iter()
+        .map(|food| {
+            food.ingredients.iter()
+                .filter(|&ingredient| safe_ingredients.contains(ingredient.as_str()))
+                .count()
+        })
+        .sum()
+}
+
+fn solve<'a, 'b>(candidates: &'b mut Vec<(&'a str, HashSet<&'a str>)>, solution: &'b mut Vec<(&'a str, &'a str)>) -> bool {
+    let i = solution.len();
+    if i == candidates.len() {
+        return true;
+    }
+
+    if candidates[i].1.is_empty() {
+        return false;
+    }
+
+    for ingredient in candidates[i].1.iter().copied().collect::<Vec<&str>>() {
+        let mut removed_from = Vec::<usize>::new();
+        for j in (i + 1)..candidates.len() {
+            if candidates[j].1.remove(ingredient) {
+                removed_from.push(j);
+            }
+        }
+        solution.push((candidates[i].0, ingredient));
+        if solve(candidates, solution) {
+            return true;
+        }
+        solution.pop();
+        for j in removed_from {
+            candidates[j].1.insert(ingredient);
+        }
+    }
+
+    false
+}
+
+fn part2(foods: &[Food]) -> String {
+    let mut candidates = candidates(foods).iter()
+        .map(|(&allergen, ingredients)| (allergen, ingredients.iter().copied().collect::<HashSet<&str>>()))
+        .collect::<Vec<(&str, HashSet<&str>)>>();
+    candidates.sort_by_key(|(_, ingredients)| ingredients.len());
+
+    let mut solution = Vec::<(&str, &str)>::new();
+    let success = solve(&mut candidates, &mut solution);
+    assert!(success);
+
+    solution.sort();
+    solution.iter()
+        .map(|(_, ingredient)| ingredient)
+        .join(",")
+}
+
+/// A random lowercase word that is not in `used` yet, which it is then added to.
+fn unique_word(rng: &mut Rng, used: &mut HashSet<String>) -> String {
+    loop {
+        let word = (0..rng.range(4..9)).map(|_| (b'a' + rng.index(26) as u8) as char).collect::<String>();
+        if used.insert(word.clone()) {
+            return word;
+        }
+    }
+}
+
+/// Whether every allergen can be pinned to one ingredient, by repeatedly taking an allergen with a
+/// single candidate and removing that ingredient from the candidates of the others.
+fn resolves(candidates: &[HashSet<usize>]) -> bool {
+    let mut candidates = candidates.to_vec();
+    let mut resolvable = (0..candidates.len()).filter(|&a| candidates[a].len() == 1).collect::<Vec<_>>();
+    let mut num_resolved = 0;
+    while let Some(a) = resolvable.pop() {
+        num_resolved += 1;
+        let ingredient = *candidates[a].iter().next().unwrap();
+        for (b, other_candidates) in candidates.iter_mut().enumerate() {
+            if b != a && other_candidates.remove(&ingredient) && other_candidates.len() == 1 {
+                resolvable.push(b);
+            }
+        }
+    }
+    num_resolved == candidates.len()
+}
+
+/// Generates foods with `size` allergens, which are each in a different ingredient, and twice as
+/// many safe ingredients. Foods are added until the allergens can be worked out one at a time, as
+/// in the real input. Returns the input with the answers to both parts.
+fn generate(rng: &mut Rng, size: usize) -> (String, usize, String) {
+    let mut used_words = HashSet::new();
+    let allergens = (0..size).map(|_| unique_word(rng, &mut used_words)).collect::<Vec<_>>();
+    // Ingredient `a` contains allergen `a`, and the others are safe.
+    let ingredients = (0..3 * size).map(|_| unique_word(rng, &mut used_words)).collect::<Vec<_>>();
+    let max_extra_ingredients = (ingredients.len() as i64 / 2).min(15).max(2);
+
+    let mut foods = Vec::<(Vec<usize>, Vec<usize>)>::new();
+    let mut candidates = vec![None::<HashSet<usize>>; size];
+    loop {
+        for a in 0..size {
+            let mut food_allergens = vec![a];
+            for _ in 0..rng.index(3) {
+                let b = rng.index(size);
+                if !food_allergens.contains(&b) {
+                    food_allergens.push(b);
+                }
+            }
+            let mut food_ingredients = food_allergens.clone();
+            for _ in 0..rng.range(1..max_extra_ingredients) {
+                let i = rng.index(ingredients.len());
+                if !food_ingredients.contains(&i) {
+                    food_ingredients.push(i);
+                }
+            }
+            rng.shuffle(&mut food_ingredients);
+            let food_ingredient_set = food_ingredients.iter().copied().collect::<HashSet<_>>();
+            for &b in &food_allergens {
+                candidates[b] = Some(match candidates[b].take() {
+                    Some(candidates) => candidates.intersection(&food_ingredient_set).copied().collect(),
+                    None => food_ingredient_set.clone(),
+                });
+            }
+            foods.push((food_ingredients, food_allergens));
+        }
+
+        let candidates = candidates.iter().map(|candidates| candidates.clone().unwrap()).collect::<Vec<_>>();
+        let possibly_unsafe = candidates.iter().flatten().copied().collect::<HashSet<_>>();
+        let num_safe = foods.iter()
+            .flat_map(|(food_ingredients, _)| food_ingredients)
+            .filter(|i| !possibly_unsafe.contains(i))
+            .count();
+        if num_safe > 0 && resolves(&candidates) {
+            let input = foods.iter()
+                .map(|(food_ingredients, food_allergens)| format!(
+                    "{} (contains {})\n",
+                    food_ingredients.iter().map(|&i| &ingredients[i]).join(" "),
+                    food_allergens.iter().map(|&a| &allergens[a]).join(", ")))
+                .collect();
+            let dangerous = (0..size)
+                .sorted_by_key(|&a| &allergens[a])
+                .map(|a| &ingredients[a])
+                .join(",");
+            return (input, num_safe, dangerous);
+        }
+    }
+}
+
+#[test]
+fn test_generate() {
+    for size in 1..=30 {
+        let (input, num_safe, dangerous) = generate(&mut Rng::new(size as u64), size);
+        assert_eq!(generate(&mut Rng::new(size as u64), size).0, input);
+        let foods = parse(&input).unwrap();
+        assert_eq!((part1(&foods), part2(&foods)), (num_safe, dangerous), "size {}", size);
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 21;
+
+    type Input = Vec<Food>;
+    type Output1 = usize;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Vec<Food>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Vec<Food>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &Vec<Food>) -> String {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size).0)
+    }
+}
+
+aoc::solution_tests! {
+    Day21,
+    example_0 { 1, 2 },
+    input { 1 => 2389, 2 => "fsr,skrxt,lqbcg,mgbv,dvjrrkv,ndnlm,xcljh,zbhp" },
+}
@@ -0,0 +1,540 @@
+use aoc::random::Rng;
+use aoc::{parse, ParseError, Solution};
+use packed_simd::{shuffle, Simd};
+use std::collections::{HashSet, VecDeque};
+use std::convert::TryFrom;
+use std::fmt::Display;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+
+type Card = u8;
+
+const NO_CARD: Card = 0;
+
+// There are 50 cards. We can hold at most DECK_SIZE - 1 and it must be a power of two.
+const DECK_SIZE: usize = 64;
+
+type Cards = Simd<[Card; DECK_SIZE]>;
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Decks {
+    cards: Cards,
+}
+
+const NO_CARDS: Cards = Cards::splat(NO_CARD);
+
+const CARD_INDICES_0: Cards = Cards::new(
+    00, 01, 02, 03, 04, 05, 06, 07, 08, 09, 10, 11, 12, 13, 14, 15,
+    16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31,
+    32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47,
+    48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63);
+
+const CARD_INDICES_1: Cards = Cards::new(
+    63, 62, 61, 60, 59, 58, 57, 56, 55, 54, 53, 52, 51, 50, 49, 48,
+    47, 46, 45, 44, 43, 42, 41, 40, 39, 38, 37, 36, 35, 34, 33, 32,
+    31, 30, 29, 28, 27, 26, 25, 24, 23, 22, 21, 20, 19, 18, 17, 16,
+    15, 14, 13, 12, 11, 10, 09, 08, 07, 06, 05, 04, 03, 02, 01, 00);
+
+/// Parses the decks of both players, without limits on their size.
+fn parse_decks(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let mut player = 0;
+    let decks = parse::blocks::<_, Vec<Vec<usize>>, _>(input, |block| {
+        player += 1;
+        let (header, cards) = parse::split_once(block, "\n")?;
+        if header != format!("Player {}:", player) {
+            return Err(ParseError::at(block, header, format!("expected \"Player {}:\"", player)));
+        }
+        parse::lines(cards, |line| match parse::value(line)? {
+            0 => Err(ParseError::at(line, line, "expected a positive card")),
+            card => Ok(card),
+        }).map_err(|err| err.within(block, cards))
+    })?;
+    if decks.len() != 2 {
+        return Err(ParseError::at(input, input, "expected two decks"));
+    }
+    Ok(decks)
+}
+
+impl Decks {
+    #[cfg(test)]
+    fn parse(input: &str) -> Result<Decks, ParseError> {
+        let decks = parse_decks(input)?;
+        Decks::from_cards(&decks[0], &decks[1]).ok_or_else(|| ParseError::at(
+            input, input, format!("expected fewer than {} cards in total, up to {}", DECK_SIZE, Card::MAX)))
+    }
+
+    /// Returns `None` if the cards don't fit.
+    fn from_cards(cards_0: &[usize], cards_1: &[usize]) -> Option<Decks> {
+        if cards_0.len() + cards_1.len() >= DECK_SIZE {
+            return None;
+        }
+        let to_cards = |cards: &[usize]| cards.iter().map(|&card| Card::try_from(card).ok()).collect::<Option<Vec<_>>>();
+        Some(Decks::new(&to_cards(cards_0)?, &to_cards(cards_1)?))
+    }
+
+    fn new(cards_0: &[Card], cards_1: &[Card]) -> Decks {
+        let mut cards = [NO_CARD; 64];
+        for (i, &card) in cards_0.iter().enumerate() {
+            cards[i] = card;
+        }
+        for (i, &card) in cards_1.iter().enumerate() {
+            debug_assert!(cards[DECK_SIZE - 1 - i] == NO_CARD);
+            cards[DECK_SIZE - 1 - i] = card;
+        }
+        Decks { cards: Cards::from_slice_unaligned(&cards) }
+    }
+
+    fn is_any_empty(&self) -> bool {
+        self.is_empty_0() || self.is_empty_1()
+    }
+
+    fn is_empty_0(&self) -> bool {
+        NO_CARD == unsafe { self.cards.extract_unchecked(0) }
+    }
+
+    fn is_empty_1(&self) -> bool {
+        NO_CARD == unsafe { self.cards.extract_unchecked(DECK_SIZE - 1) }
+    }
+
+    fn len_0(&self) -> u32 {
+        self.cards.ne(NO_CARDS).bitmask().trailing_ones()
+    }
+
+    fn len_1(&self) -> u32 {
+        self.cards.ne(NO_CARDS).bitmask().leading_ones()
+    }
+
+    fn cards_0(&self) -> Vec<Card> {
+        let mut cards = [NO_CARD; DECK_SIZE];
+        unsafe {
+            self.cards.write_to_slice_unaligned_unchecked(&mut cards);
+        }
+        cards.iter().copied().take_while(|&card| card != NO_CARD).collect()
+    }
+
+    fn cards_1(&self) -> Vec<Card> {
+        let mut cards = [NO_CARD; DECK_SIZE];
+        unsafe {
+            self.cards.write_to_slice_unaligned_unchecked(&mut cards);
+        }
+        cards.iter().copied().rev().take_while(|&card| card != NO_CARD).collect()
+    }
+
+    fn push_back_0(&mut self, a: Card, b: Card) {
+        debug_assert!((self.len_0() as usize) + (self.len_1() as usize) < DECK_SIZE - 2);
+        let idx = self.len_0() as usize;
+        self.cards = unsafe { self.cards.replace_unchecked(idx, a).replace_unchecked(idx + 1, b) };
+    }
+
+    fn push_back_1(&mut self, a: Card, b: Card) {
+        debug_assert!((self.len_0() as usize) + (self.len_1() as usize) < DECK_SIZE - 2);
+        let idx = DECK_SIZE - 1 - self.len_1() as usize;
+        self.cards = unsafe { self.cards.replace_unchecked(idx, a).replace_unchecked(idx - 1, b) };
+    }
+
+    fn pop_both(&mut self) -> (Card, Card) {
+        let card_0 = unsafe { self.cards.extract_unchecked(0) };
+        let card_1 = unsafe { self.cards.extract_unchecked(63) };
+        debug_assert!(card_0 != NO_CARD);
+        debug_assert!(card_1 != NO_CARD);
+        let popped_0 = shuffle!(self.cards, [
+            01, 02, 03, 04, 05, 06, 07, 08, 09, 10, 11, 12, 13, 14, 15, 16,
+            17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32,
+            33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48,
+            49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 63
+        ]);
+        let popped_1 = shuffle!(self.cards, [
+            00, 00, 01, 02, 03, 04, 05, 06, 07, 08, 09, 10, 11, 12, 13, 14,
+            15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30,
+            31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46,
+            47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62
+        ]);
+        self.cards = CARD_INDICES_0.lt(Cards::splat(self.len_0() as u8 - 1)).select(
+            popped_0,
+            CARD_INDICES_1.lt(Cards::splat(self.len_1() as u8 - 1)).select(popped_1, NO_CARDS));
+        (card_0, card_1)
+    }
+
+    fn tops(&self, count_0: u8, count_1: u8) -> Decks {
+        debug_assert!(count_0 as u32 <= self.len_0());
+        debug_assert!(count_1 as u32 <= self.len_1());
+        let mask =
+            CARD_INDICES_0.lt(Cards::splat(count_0)) |
+            CARD_INDICES_1.lt(Cards::splat(count_1));
+        Decks { cards: mask.select(self.cards, NO_CARDS) }
+    }
+}
+
+#[test]
+fn test_deck_queries() {
+    let empty_both = Decks::new(&[], &[]);
+    assert!(empty_both.is_any_empty());
+    assert!(empty_both.is_empty_0());
+    assert!(empty_both.is_empty_1());
+    assert_eq!(empty_both.len_0(), 0);
+    assert_eq!(empty_both.len_1(), 0);
+    assert_eq!(empty_both.cards_0(), Vec::<Card>::new());
+    assert_eq!(empty_both.cards_1(), Vec::<Card>::new());
+
+    let empty_0 = Decks::new(&[], &[42]);
+    assert!(empty_0.is_any_empty());
+    assert!(empty_0.is_empty_0());
+    assert!(!empty_0.is_empty_1());
+    assert_eq!(empty_0.len_0(), 0);
+    assert_eq!(empty_0.len_1(), 1);
+    assert_eq!(empty_0.cards_0(), Vec::<Card>::new());
+    assert_eq!(empty_0.cards_1(), vec![42]);
+
+    let empty_1 = Decks::new(&[42], &[]);
+    assert!(empty_1.is_any_empty());
+    assert!(!empty_1.is_empty_0());
+    assert!(empty_1.is_empty_1());
+    assert_eq!(empty_1.len_0(), 1);
+    assert_eq!(empty_1.len_1(), 0);
+    assert_eq!(empty_1.cards_0(), vec![42]);
+    assert_eq!(empty_1.cards_1(), Vec::<Card>::new());
+
+    let empty_neither = Decks::new(&[42], &[37]);
+    assert!(!empty_neither.is_any_empty());
+    assert!(!empty_neither.is_empty_0());
+    assert!(!empty_neither.is_empty_1());
+    assert_eq!(empty_neither.len_0(), 1);
+    assert_eq!(empty_neither.len_1(), 1);
+    assert_eq!(empty_neither.cards_0(), vec![42]);
+    assert_eq!(empty_neither.cards_1(), vec![37]);
+
+    let decks = Decks::new(&[1, 2, 3], &[4, 5, 6]);
+    assert!(!decks.is_any_empty());
+    assert!(!decks.is_empty_0());
+    assert!(!decks.is_empty_1());
+    assert_eq!(decks.len_0(), 3);
+    assert_eq!(decks.len_1(), 3);
+    assert_eq!(decks.cards_0(), vec![1, 2, 3]);
+    assert_eq!(decks.cards_1(), vec![4, 5, 6]);
+}
+
+fn score<C: Into<usize>>(cards: Vec<C>) -> u64 {
+    cards
+        .into_iter()
+        .rev()
+        .zip(1..)
+        .map(|(c, i)| c.into() as u64 * i as u64)
+        .sum()
+}
+
+fn part1(decks: &Decks) -> u64 {
+    let mut decks = decks.clone();
+
+    while !decks.is_any_empty() {
+        let (card_0, card_1) = decks.pop_both();
+        if card_0 > card_1 {
+            decks.push_back_0(card_0, card_1);
+        } else {
+            debug_assert!(card_0 < card_1);
+            decks.push_back_1(card_1, card_0);
+        }
+    }
+
+    score(decks.cards_0()) + score(decks.cards_1())
+}
+
+#[test]
+fn test_parse_error() {
+    assert!(Decks::parse("Player 1:\n9\n2\n\nPlayer 2:\n5\n8\n").is_ok());
+    assert_eq!(
+        Decks::parse("Player 1:\n9\n2\n\nPlayer 2:\n5\n-8\n").err(),
+        Some(ParseError::new(7, 1, "invalid value \"-8\": invalid digit found in string")));
+    assert_eq!(
+        Decks::parse("Player 1:\n9\n2\n\nPlayer 3:\n5\n8\n").err(),
+        Some(ParseError::new(5, 1, "expected \"Player 2:\"")));
+}
+
+impl Hash for Decks {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        unsafe {
+            std::mem::transmute::<&Cards, &[u8; 64]>(&self.cards).hash(h)
+        }
+    }
+}
+
+fn recursive_game(mut decks: Decks) -> (usize, Decks) {
+    let mut prev_states = HashSet::with_capacity_and_hasher(
+        512, BuildHasherDefault::<rustc_hash::FxHasher>::default());
+    
+    while !decks.is_any_empty() {
+        // Before either player deals a card, if there was a previous round in this game that had
+        // exactly the same cards in the same order in the same players' decks, the game instantly
+        // ends in a win for player 1.
+        if !prev_states.insert(decks.clone()) {
+            return (0, decks);
+        }
+        // Otherwise, this round's cards must be in a new configuration; the players begin the
+        // round by each drawing the top card of their deck as normal.
+        let (card_0, card_1) = decks.pop_both();
+        let round_winner = if decks.len_0() as Card >= card_0 && decks.len_1() as Card >= card_1 {
+            // If both players have at least as many cards remaining in their deck as the value of
+            // the card they just drew, the winner of the round is determined by playing a new game
+            // of Recursive Combat.
+            //
+            // To play a sub-game of Recursive Combat, each player creates a new deck by making a
+            // copy of the next cards in their deck (the quantity of cards copied is equal to the
+            // number on the card they drew to trigger the sub-game).
+            let (subgame_winner, _) = recursive_game(decks.tops(card_0, card_1));
+            subgame_winner
+        } else {
+            // Otherwise, at least one player must not have enough cards left in their deck to
+            // recurse; the winner of the round is the player with the higher-value card.
+            if card_0 > card_1 {
+                0
+            } else {
+                debug_assert!(card_0 < card_1);
+                1
+            }
+        };
+        match round_winner {
+            0 => decks.push_back_0(card_0, card_1),
+            1 => decks.push_back_1(card_1, card_0),
+            _ => panic!(),
+        }
+    }
+
+    let game_winner = if decks.is_empty_1() {
+        0
+    } else {
+        debug_assert!(decks.is_empty_0());
+        1
+    };
+    (game_winner, decks)
+}
+
+fn part2(decks: &Decks) -> u64 {
+    let (winner, decks) = recursive_game(decks.clone());
+
+    match winner {
+        0 => score(decks.cards_0()),
+        1 => score(decks.cards_1()),
+        _ => panic!(),
+    }
+}
+
+/// Plays Combat, or Recursive Combat, the obvious way. Returns the winner and their deck, or `None`
+/// if a game of plain Combat goes on forever. Unlike `Decks`, this works for any number of cards,
+/// so it plays the games that don't fit in `Decks`. Sub-games that `player_0_wins` are skipped,
+/// without which large games take minutes.
+fn reference_game<C>(mut decks: [VecDeque<C>; 2], recursive: bool) -> Option<(usize, Vec<C>)>
+    where C: Copy + Ord + Hash + Into<usize>
+{
+    let mut prev_states = HashSet::new();
+    while !decks[0].is_empty() && !decks[1].is_empty() {
+        if !prev_states.insert(decks.clone()) {
+            return if recursive { Some((0, decks[0].iter().copied().collect())) } else { None };
+        }
+        let cards = [decks[0].pop_front().unwrap(), decks[1].pop_front().unwrap()];
+        let round_winner = if recursive && decks[0].len() >= cards[0].into() && decks[1].len() >= cards[1].into() {
+            let sub_decks = [
+                decks[0].iter().copied().take(cards[0].into()).collect(),
+                decks[1].iter().copied().take(cards[1].into()).collect(),
+            ];
+            if player_0_wins(&sub_decks) { 0 } else { reference_game(sub_decks, true)?.0 }
+        } else if cards[0] > cards[1] {
+            0
+        } else {
+            1
+        };
+        decks[round_winner].push_back(cards[round_winner]);
+        decks[round_winner].push_back(cards[1 - round_winner]);
+    }
+    let winner = if decks[0].is_empty() { 1 } else { 0 };
+    Some((winner, decks[winner].iter().copied().collect()))
+}
+
+/// Whether player 1 is sure to win a sub-game of Recursive Combat: they hold a card that beats all
+/// of player 2's cards, and that is too high to start another sub-game. They never lose it, so the
+/// game either ends with their win, or repeats, which is also their win.
+fn player_0_wins<C: Copy + Ord + Into<usize>>(decks: &[VecDeque<C>; 2]) -> bool {
+    match (decks[0].iter().max(), decks[1].iter().max()) {
+        (Some(&max_0), Some(&max_1)) => max_0 > max_1 && max_0.into() + 2 > decks[0].len() + decks[1].len(),
+        _ => false,
+    }
+}
+
+/// Whether the decks are a valid input, which can only be told by playing a game of Combat.
+fn combat_ends<C>((deck_0, deck_1): &(Vec<C>, Vec<C>)) -> bool
+    where C: Copy + Ord + Hash + Into<usize>
+{
+    reference_game([deck_0.iter().copied().collect(), deck_1.iter().copied().collect()], false).is_some()
+}
+
+fn format_decks<C: Display>(deck_0: &[C], deck_1: &[C]) -> String {
+    let format_deck = |deck: &[C]| deck.iter().map(|card| format!("{}\n", card)).collect::<String>();
+    format!("Player 1:\n{}\nPlayer 2:\n{}", format_deck(deck_0), format_deck(deck_1))
+}
+
+/// Generates decks with the cards 1 to `size`, dealt into two halves, such that plain Combat ends.
+/// More than 63 cards are played without `Decks`, and Recursive Combat can then take minutes.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    loop {
+        let mut deck_0 = (1..=size).collect::<Vec<_>>();
+        rng.shuffle(&mut deck_0);
+        let deck_1 = deck_0.split_off(size / 2);
+        let decks = (deck_0, deck_1);
+        if combat_ends(&decks) {
+            return format_decks(&decks.0, &decks.1);
+        }
+    }
+}
+
+#[test]
+fn test_generate() {
+    for size in 2..=20 {
+        let input = generate(&mut Rng::new(size as u64), size);
+        assert_eq!(generate(&mut Rng::new(size as u64), size), input);
+        let decks = Decks::parse(&input).unwrap();
+        assert_eq!(decks.cards_0().len() + decks.cards_1().len(), size);
+        let reference_decks = [decks.cards_0().into_iter().collect::<VecDeque<_>>(), decks.cards_1().into_iter().collect()];
+        assert_eq!(
+            (score(reference_game(reference_decks.clone(), false).unwrap().1), score(reference_game(reference_decks, true).unwrap().1)),
+            (part1(&decks), part2(&decks)));
+    }
+
+    // Too many cards for `Decks`, but they are still played.
+    let game = Game::parse(&generate(&mut Rng::new(0), 100)).unwrap();
+    assert!(matches!(game, Game::Large(_)));
+    assert!(game.part1().is_ok());
+    game.part2();
+}
+
+/// Checks the SIMD `Decks` against decks in a `VecDeque`, on both parts.
+#[cfg(test)]
+struct DecksDifferential;
+
+#[cfg(test)]
+impl aoc::differential::Differential for DecksDifferential {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 22;
+    const NAME: &'static str = "decks";
+
+    type Case = (Vec<Card>, Vec<Card>);
+    type Output = (u64, u64);
+
+    fn generate(rng: &mut Rng) -> (Vec<Card>, Vec<Card>) {
+        loop {
+            let num_cards = rng.range(2..16) as Card;
+            let mut cards = (1..=num_cards).collect::<Vec<_>>();
+            rng.shuffle(&mut cards);
+            let split = rng.range(1..num_cards as i64) as usize;
+            let decks = (cards[..split].to_vec(), cards[split..].to_vec());
+            if combat_ends(&decks) {
+                return decks;
+            }
+        }
+    }
+
+    /// Removes single cards, keeping both decks non-empty, because the input format can't express
+    /// an empty deck, and keeping games of Combat finite.
+    fn shrink((deck_0, deck_1): &(Vec<Card>, Vec<Card>)) -> Vec<(Vec<Card>, Vec<Card>)> {
+        let shrink_deck = |deck: &[Card]| aoc::differential::shrink_vec(deck).into_iter().filter(|deck| !deck.is_empty());
+        shrink_deck(deck_0).map(|deck_0| (deck_0, deck_1.clone()))
+            .chain(shrink_deck(deck_1).map(|deck_1| (deck_0.clone(), deck_1)))
+            .filter(combat_ends)
+            .collect()
+    }
+
+    fn optimized((deck_0, deck_1): &(Vec<Card>, Vec<Card>)) -> (u64, u64) {
+        let decks = Decks::new(deck_0, deck_1);
+        (part1(&decks), part2(&decks))
+    }
+
+    fn reference((deck_0, deck_1): &(Vec<Card>, Vec<Card>)) -> (u64, u64) {
+        let decks = [deck_0.iter().copied().collect(), deck_1.iter().copied().collect()];
+        (score(reference_game(decks.clone(), false).unwrap().1), score(reference_game(decks, true).unwrap().1))
+    }
+
+    fn to_input((deck_0, deck_1): &(Vec<Card>, Vec<Card>)) -> String {
+        format_decks(deck_0, deck_1)
+    }
+
+    fn from_input(input: &str) -> Result<(Vec<Card>, Vec<Card>), ParseError> {
+        Decks::parse(input).map(|decks| (decks.cards_0(), decks.cards_1()))
+    }
+}
+
+#[test]
+fn test_decks_differential() {
+    aoc::differential::check::<DecksDifferential>(300);
+}
+
+/// The decks to play with: `Decks` if they fit, and otherwise a `VecDeque` per player, which is
+/// much slower but holds any number of cards.
+pub enum Game {
+    Simd(Decks),
+    Large([VecDeque<usize>; 2]),
+}
+
+impl Game {
+    fn parse(input: &str) -> Result<Game, ParseError> {
+        let decks = parse_decks(input)?;
+        Ok(match Decks::from_cards(&decks[0], &decks[1]) {
+            Some(decks) => Game::Simd(decks),
+            None => Game::Large([decks[0].iter().copied().collect(), decks[1].iter().copied().collect()]),
+        })
+    }
+
+    fn part1(&self) -> Result<u64, &'static str> {
+        match self {
+            Game::Simd(decks) => Ok(part1(decks)),
+            Game::Large(decks) => reference_game(decks.clone(), false)
+                .map(|(_, deck)| score(deck))
+                .ok_or("the game of Combat never ends"),
+        }
+    }
+
+    fn part2(&self) -> u64 {
+        match self {
+            Game::Simd(decks) => part2(decks),
+            Game::Large(decks) => score(reference_game(decks.clone(), true).unwrap().1),
+        }
+    }
+}
+
+#[test]
+fn test_game() {
+    let input = "Player 1:\n9\n2\n6\n3\n1\n\nPlayer 2:\n5\n8\n4\n7\n10\n";
+    let large = Game::parse(&input.replace("10\n", "300\n")).unwrap();
+    assert!(matches!(Game::parse(input).unwrap(), Game::Simd(_)));
+    assert!(matches!(large, Game::Large(_)));
+    assert_eq!((large.part1(), large.part2()), (Ok(306 + 8 * 290), 291 + 4 * 290));
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 22;
+
+    type Input = Game;
+    type Output1 = Result<u64, &'static str>;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Game, ParseError> {
+        Game::parse(input)
+    }
+
+    fn part1(input: &Game) -> Result<u64, &'static str> {
+        input.part1()
+    }
+
+    fn part2(input: &Game) -> u64 {
+        input.part2()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc::solution_tests! {
+    Day22,
+    example_0 { 1, 2 },
+    example_3 { 2 },
+    input { 1 => 33098, 2 => 35055 },
+}
@@ -0,0 +1,248 @@
+use aoc::{ParseError, Solution};
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cup(u32);
+
+impl Cup {
+    fn from_index(index: usize) -> Cup {
+        Cup(index as u32)
+    }
+
+    fn from_label(label: usize) -> Cup {
+        Self::from_index(label - 1)
+    }
+
+    fn from_digit(digit: char) -> Cup {
+        Self::from_label(digit.to_digit(10).unwrap() as usize)
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+    
+    fn label(self) -> u32 {
+        self.0 + 1
+    }
+
+    fn minus_one(self, num_cups: u32) -> Cup {
+        Cup((self.0 + num_cups - 1) % num_cups)
+    }
+}
+
+impl std::fmt::Display for Cup {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+struct Cups {
+    next: Vec<Cup>,
+}
+
+impl Cups {
+    fn new(cups: &[Cup]) -> Cups {
+        let num_cups = cups.len();
+        let mut next = (0..num_cups)
+            .map(|idx| Cup::from_index(idx))
+            .collect::<Vec<_>>();
+        for i in 0..(num_cups - 1) {
+            next[cups[i].index()] = cups[i + 1];
+        }
+        next[cups[num_cups - 1].index()] = cups[0];
+        Cups { next }
+    }
+
+    fn num_cups(&self) -> usize {
+        self.next.len()
+    }
+
+    fn next(&self, cup: Cup) -> Cup {
+        self.next[cup.index()]
+    }
+
+    fn link(&mut self, a: Cup, b: Cup) {
+        self.next[a.index()] = b;
+    }
+
+    fn iter_from<'a>(&'a self, from: Cup) -> CupsIterator<'a> {
+        CupsIterator { cups: self, start: from, next: Some(from) }
+    }
+}
+
+struct CupsIterator<'a> {
+    cups: &'a Cups,
+    start: Cup,
+    next: Option<Cup>,
+}
+
+impl Iterator for CupsIterator<'_> {
+    type Item = Cup;
+    fn next(&mut self) -> Option<Cup> {
+        let next = self.next;
+        if let Some(next) = next {
+            let new_next = self.cups.next(next);
+            self.next = if new_next != self.start { Some(new_next) } else { None };
+        }
+        next
+    }
+}
+
+fn play(cups: &mut Cups, mut curr: Cup, num_rounds: usize) {
+    let num_cups = cups.num_cups() as u32;
+    for _ in 0..num_rounds {
+        let a = cups.next(curr);
+        let b = cups.next(a);
+        let c = cups.next(b);
+        cups.link(curr, cups.next(c));
+        let mut dest = curr.minus_one(num_cups);
+        while dest == a || dest == b || dest == c {
+            dest = dest.minus_one(num_cups);
+        }
+        let dest_next = cups.next(dest);
+        cups.link(dest, a);
+        cups.link(c, dest_next);
+        curr = cups.next(curr);
+    }
+}
+
+fn part1(input: &str) -> String {
+    let init_cups = input.trim().chars().map(Cup::from_digit).collect::<Vec<_>>();
+
+    let mut cups = Cups::new(&init_cups);
+    play(&mut cups, init_cups[0], 100);
+
+    cups.iter_from(Cup::from_label(1)).skip(1).join("")
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1("389125467\n"), "67384529");
+}
+
+fn part2(input: &str) -> u64 {
+    let mut init_cups = input.trim().chars().map(Cup::from_digit).collect::<Vec<_>>();
+    init_cups.extend((init_cups.len()..1_000_000).map(Cup::from_index));
+
+    let mut cups = Cups::new(&init_cups);
+    play(&mut cups, init_cups[0], 10_000_000);
+    
+    cups.iter_from(Cup::from_label(1)).skip(1).take(2).map(|cup| cup.label() as u64).product()
+}
+
+#[test]
+fn test_part2() {
+    assert_eq!(part2("389125467\n"), 149245887792);
+}
+
+/// Plays part 1 the obvious way, keeping the cups in a `Vec` with the current cup first.
+#[cfg(test)]
+fn reference_part1(labels: &[u32]) -> String {
+    let num_cups = labels.len() as u32;
+    let mut cups = labels.to_vec();
+    for _ in 0..100 {
+        let picked_up = cups.drain(1..4).collect::<Vec<_>>();
+        let mut dest = cups[0];
+        loop {
+            dest = if dest == 1 { num_cups } else { dest - 1 };
+            if !picked_up.contains(&dest) {
+                break;
+            }
+        }
+        let dest_index = cups.iter().position(|&cup| cup == dest).unwrap();
+        cups.splice(dest_index + 1..dest_index + 1, picked_up);
+        cups.rotate_left(1);
+    }
+    let one_index = cups.iter().position(|&cup| cup == 1).unwrap();
+    cups.rotate_left(one_index);
+    cups[1..].iter().join("")
+}
+
+/// Checks the linked list in `Cups` against moving cups around in a `Vec`.
+#[cfg(test)]
+struct CupsDifferential;
+
+#[cfg(test)]
+impl aoc::differential::Differential for CupsDifferential {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 23;
+    const NAME: &'static str = "cups";
+
+    /// The labels, which are 1 up to the number of cups, in some order.
+    type Case = Vec<u32>;
+    type Output = String;
+
+    fn generate(rng: &mut aoc::random::Rng) -> Vec<u32> {
+        let mut labels = (1..=rng.range(5..10) as u32).collect::<Vec<_>>();
+        rng.shuffle(&mut labels);
+        labels
+    }
+
+    /// Removes the highest label, or swaps two neighbouring labels that are out of order. At least
+    /// 5 cups are needed to pick up 3 and still have a destination.
+    fn shrink(labels: &Vec<u32>) -> Vec<Vec<u32>> {
+        let without_highest = Some(labels)
+            .filter(|labels| labels.len() > 5)
+            .map(|labels| labels.iter().copied().filter(|&label| label != labels.len() as u32).collect());
+        let sorted_pairs = (1..labels.len())
+            .filter(|&i| labels[i - 1] > labels[i])
+            .map(|i| {
+                let mut swapped = labels.clone();
+                swapped.swap(i - 1, i);
+                swapped
+            });
+        without_highest.into_iter().chain(sorted_pairs).collect()
+    }
+
+    fn optimized(labels: &Vec<u32>) -> String {
+        part1(&labels.iter().join(""))
+    }
+
+    fn reference(labels: &Vec<u32>) -> String {
+        reference_part1(labels)
+    }
+
+    fn to_input(labels: &Vec<u32>) -> String {
+        format!("{}\n", labels.iter().join(""))
+    }
+
+    fn from_input(input: &str) -> Result<Vec<u32>, ParseError> {
+        let line = input.trim_end();
+        line.char_indices()
+            .map(|(i, c)| c.to_digit(10).ok_or_else(|| ParseError::at(line, &line[i..], format!("expected a digit, found {:?}", c))))
+            .collect()
+    }
+}
+
+#[test]
+fn test_cups_differential() {
+    aoc::differential::check::<CupsDifferential>(300);
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 23;
+
+    type Input = String;
+    type Output1 = String;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<String, ParseError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &String) -> String {
+        part1(input)
+    }
+
+    fn part2(input: &String) -> u64 {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day23,
+    input { 1 => "46978532" },
+}
@@ -0,0 +1,82 @@
+use aoc::automaton::{Automaton, Hex, LifeRule, Sparse};
+use aoc::{parse, ParseError, Solution};
+use std::collections::HashSet;
+
+type Coord = (i64, i64);
+
+fn final_tile(line: &str) -> Result<Coord, ParseError> {
+    let (mut x, mut y) = (0, 0);
+    let mut rest = line;
+    while !rest.is_empty() {
+        let (len, (dx, dy)) = match rest.as_bytes() {
+            [b'e', ..] => (1, (1, 0)),
+            [b'w', ..] => (1, (-1, 0)),
+            [b'n', b'e', ..] => (2, (1, -1)),
+            [b'n', b'w', ..] => (2, (0, -1)),
+            [b's', b'e', ..] => (2, (0, 1)),
+            [b's', b'w', ..] => (2, (-1, 1)),
+            _ => return Err(ParseError::at(line, rest, "expected one of e, se, sw, w, nw, ne")),
+        };
+        x += dx;
+        y += dy;
+        rest = &rest[len..];
+    }
+    Ok((x, y))
+}
+
+fn initial_black(input: &str) -> Result<HashSet<Coord>, ParseError> {
+    Ok(parse::lines::<_, Vec<_>, _>(input, final_tile)?
+        .into_iter()
+        .fold(HashSet::new(), |mut black, tile| {
+            if black.contains(&tile) {
+                black.remove(&tile);
+            } else {
+                black.insert(tile);
+            }
+            black
+        }))
+}
+
+#[test]
+fn test_parse_error() {
+    assert_eq!(initial_black("esew\nnwwswee\nnwwsxee\n").err(), Some(ParseError::new(3, 4, "expected one of e, se, sw, w, nw, ne")));
+}
+
+fn part1(black: &HashSet<Coord>) -> usize {
+    black.len()
+}
+
+fn part2(black: &HashSet<Coord>) -> usize {
+    let mut floor = Sparse::new(Hex, black.iter().copied());
+    floor.run(&"B2/S12".parse::<LifeRule>().unwrap(), 100);
+    floor.population()
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 24;
+
+    type Input = HashSet<Coord>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<HashSet<Coord>, ParseError> {
+        initial_black(input)
+    }
+
+    fn part1(input: &HashSet<Coord>) -> usize {
+        part1(input)
+    }
+
+    fn part2(input: &HashSet<Coord>) -> usize {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day24,
+    example_0 { 1, 2 },
+    input { 1 => 322, 2 => 3831 },
+}
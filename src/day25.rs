@@ -0,0 +1,57 @@
+use aoc::math;
+use aoc::{parse, ParseError, Solution};
+
+const PRIME: u64 = 20201227;
+const SUBJECT_NUMBER: u64 = 7;
+
+type PublicKeys = (u64, u64);
+
+fn parse(input: &str) -> Result<PublicKeys, ParseError> {
+    match parse::numbers::<u64, Vec<_>>(input)?[..] {
+        [a, b] => Ok((a, b)),
+        _ => Err(ParseError::at(input, input, "expected two public keys")),
+    }
+}
+
+fn part1(&(pub_key_a, pub_key_b): &PublicKeys) -> Result<u64, &'static str> {
+    let loop_size_a = math::discrete_log(SUBJECT_NUMBER, pub_key_a, PRIME).ok_or("no loop size gives the first public key")?;
+    Ok(math::mod_pow(pub_key_b, loop_size_a, PRIME))
+}
+
+#[test]
+fn test_part1() {
+    assert_eq!(part1(&parse("17807724\n5764801").unwrap()), Ok(14897079));
+    assert_eq!(part1(&parse("5764801\n17807724").unwrap()), Ok(14897079));
+}
+
+fn part2(_keys: &PublicKeys) -> String {
+    "n/a".to_string()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u32 = crate::YEAR;
+    const DAY: u32 = 25;
+
+    type Input = PublicKeys;
+    type Output1 = Result<u64, &'static str>;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<PublicKeys, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &PublicKeys) -> Result<u64, &'static str> {
+        part1(input)
+    }
+
+    fn part2(input: &PublicKeys) -> String {
+        part2(input)
+    }
+}
+
+aoc::solution_tests! {
+    Day25,
+    input { 1 => 3803729 },
+}
@@ -0,0 +1,62 @@
+#![feature(iterator_fold_self)]
+#![feature(str_split_once)]
+#![feature(try_trait)]
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+/// The Advent of Code event that these solutions are for.
+pub const YEAR: u32 = 2020;
+
+pub fn registry() -> aoc::Registry {
+    let mut registry = aoc::Registry::new(YEAR);
+    registry.register::<day01::Day01>();
+    registry.register::<day02::Day02>();
+    registry.register::<day03::Day03>();
+    registry.register::<day04::Day04>();
+    registry.register::<day05::Day05>();
+    registry.register::<day06::Day06>();
+    registry.register::<day07::Day07>();
+    registry.register::<day08::Day08>();
+    registry.register::<day09::Day09>();
+    registry.register::<day10::Day10>();
+    registry.register::<day11::Day11>();
+    registry.register::<day12::Day12>();
+    registry.register::<day13::Day13>();
+    registry.register::<day14::Day14>();
+    registry.register::<day15::Day15>();
+    registry.register::<day16::Day16>();
+    registry.register::<day17::Day17>();
+    registry.register::<day18::Day18>();
+    registry.register::<day19::Day19>();
+    registry.register::<day20::Day20>();
+    registry.register::<day21::Day21>();
+    registry.register::<day22::Day22>();
+    registry.register::<day23::Day23>();
+    registry.register::<day24::Day24>();
+    registry.register::<day25::Day25>();
+    registry
+}
@@ -0,0 +1,7 @@
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: aoc::allocations::CountingAllocator = aoc::allocations::CountingAllocator;
+
+fn main() {
+    aoc::main(&aoc2020::registry());
+}